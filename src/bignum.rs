@@ -0,0 +1,155 @@
+use openssl::{
+  bn::{BigNum, BigNumContext, BigNumRef},
+  error::ErrorStack,
+};
+
+pub fn from_u64(n: u64) -> Result<BigNum, ErrorStack> {
+  BigNum::from_slice(&n.to_be_bytes())
+}
+
+pub fn to_u64(n: &BigNumRef) -> Option<u64> {
+  if n.is_negative() || n.num_bytes() > 8 {
+    return None;
+  }
+  let bytes = n.to_vec();
+  let mut padded = vec![0u8; 8 - bytes.len()];
+  padded.extend_from_slice(&bytes);
+  Some(u64::from_be_bytes(padded.try_into().unwrap()))
+}
+
+pub fn mod_exp(
+  base: &BigNumRef,
+  exponent: &BigNumRef,
+  modulus: &BigNumRef,
+) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.mod_exp(base, exponent, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+pub fn mod_mul(a: &BigNumRef, b: &BigNumRef, modulus: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.mod_mul(a, b, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+pub fn mod_sub(a: &BigNumRef, b: &BigNumRef, modulus: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.mod_sub(a, b, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+// always returns a value in [0, modulus), unlike the `%` operator which keeps the sign of `a`
+pub fn nnmod(a: &BigNumRef, modulus: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.nnmod(a, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+pub fn mod_inverse(a: &BigNumRef, modulus: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  let reduced = nnmod(a, modulus)?;
+  res.mod_inverse(&reduced, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+pub fn random_below(limit: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut res = BigNum::new()?;
+  limit.rand_range(&mut res)?;
+  Ok(res)
+}
+
+// ceil(a / b) for non-negative a and positive b
+pub fn ceil_div(a: &BigNumRef, b: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let one = BigNum::from_u32(1)?;
+  Ok(&(&(a + b) - &one) / b)
+}
+
+pub fn mod_add(a: &BigNumRef, b: &BigNumRef, modulus: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.mod_add(a, b, modulus, &mut ctx)?;
+  Ok(res)
+}
+
+// square root modulo an odd prime, None when `a` is not a quadratic residue
+pub fn mod_sqrt(a: &BigNumRef, p: &BigNumRef) -> Result<Option<BigNum>, ErrorStack> {
+  let a = nnmod(a, p)?;
+  let one = BigNum::from_u32(1)?;
+  let half = &(p - &one) >> 1;
+  if a.num_bits() != 0 && mod_exp(&a, &half, p)? != one {
+    return Ok(None);
+  }
+  let mut ctx = BigNumContext::new()?;
+  let mut res = BigNum::new()?;
+  res.mod_sqrt(&a, p, &mut ctx)?;
+  Ok(Some(res))
+}
+
+// combines x = r_i mod m_i for pairwise coprime m_i, returns (x, product of the m_i)
+pub fn crt(residues: &[(BigNum, BigNum)]) -> Result<(BigNum, BigNum), ErrorStack> {
+  let mut x = BigNum::from_u32(0)?;
+  let mut modulus = BigNum::from_u32(1)?;
+  for (r, m) in residues {
+    // x + modulus * t = r mod m
+    let diff = mod_sub(r, &x, m)?;
+    let inverse = mod_inverse(&modulus, m)?;
+    let t = mod_mul(&diff, &inverse, m)?;
+    x = &x + &(&modulus * &t);
+    modulus = &modulus * m;
+  }
+  Ok((x, modulus))
+}
+
+// distinct prime factors of n below `bound`, by trial division
+pub fn small_factors(n: &BigNumRef, bound: u32) -> Result<Vec<u32>, ErrorStack> {
+  let mut res = vec![];
+  for candidate in 2..bound {
+    if res.iter().all(|v| candidate % v != 0) && n.mod_word(candidate)? == 0 {
+      res.push(candidate);
+    }
+  }
+  Ok(res)
+}
+
+// prime of exactly `bits` bits with (p - 1) / 2 a product of distinct primes of at most 32 bits,
+// none of them in `avoid`. Returns p and the odd prime factors of p - 1
+pub fn smooth_prime(bits: i32, avoid: &[u32]) -> Result<(BigNum, Vec<u32>), ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let one = BigNum::from_u32(1)?;
+  loop {
+    let mut factors = vec![];
+    let mut product = BigNum::from_u32(2)?;
+    while product.num_bits() < bits - 32 {
+      let r = rand::random_range(1 << 15..1 << 16);
+      if !factors.contains(&r)
+        && !avoid.contains(&r)
+        && BigNum::from_u32(r)?.is_prime(20, &mut ctx)?
+      {
+        factors.push(r);
+        product = &product * &BigNum::from_u32(r)?;
+      }
+    }
+    // the last factor makes up the remaining bits
+    for _ in 0..1000 {
+      let mut last = BigNum::new()?;
+      last.generate_prime(bits - product.num_bits(), false, None, None)?;
+      let Some(last) = to_u64(&last).map(|v| v as u32) else {
+        continue;
+      };
+      if factors.contains(&last) || avoid.contains(&last) {
+        continue;
+      }
+      let p = &(&product * &BigNum::from_u32(last)?) + &one;
+      if p.num_bits() == bits && p.is_prime(64, &mut ctx)? {
+        factors.push(last);
+        return Ok((p, factors));
+      }
+    }
+  }
+}
@@ -1,123 +1,28 @@
 use std::char;
-use std::collections::BTreeMap;
-use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::stdin;
-use std::string::FromUtf8Error;
-
-use base64::Engine;
-use base64::prelude::BASE64_STANDARD;
-use cryptopals_challeges_rs::distance::hamming_distance;
-use itertools::Itertools;
-
-use crate::cryptog;
-use crate::oracle;
-use crate::oracle::oracle_create_token;
-use crate::oracle::oracle_parse_token;
-use crate::oracle_hacker;
-use crate::oracle_hacker::detect_ecb;
-use crate::xor;
-
-use crate::htb64;
-
-fn character_frequency_score(sample: String) -> u64 {
-  let freq_table: BTreeMap<u8, u64> = BTreeMap::from([
-    (b'E', 1270),
-    (b'T', 910),
-    (b'A', 820),
-    (b'O', 750),
-    (b'I', 700),
-    (b'N', 670),
-    (b'S', 630),
-    (b'H', 610),
-    (b'R', 600),
-    (b'D', 430),
-    (b'L', 400),
-    (b'C', 280),
-    (b'U', 280),
-    (b'M', 240),
-    (b'W', 240),
-    (b'F', 220),
-    (b'G', 200),
-    (b'Y', 200),
-    (b'P', 190),
-    (b'B', 150),
-    (b'V', 98),
-    (b'K', 77),
-    (b'J', 16),
-    (b'X', 15),
-    (b'Q', 12),
-    (b'Z', 7),
-  ]);
-  let mut sample_freq_table: HashMap<u8, u64> = HashMap::new();
-  let total_points: u64 = freq_table.iter().map(|(_, v)| v).sum();
-  sample.to_ascii_uppercase().as_bytes().iter().filter(|c| c.is_ascii_alphabetic()).for_each(|c| {
-    sample_freq_table.entry(c.to_ascii_uppercase()).and_modify(|v| *v += 1).or_insert(1);
-  });
-
-  freq_table
-    .iter()
-    .map(|(k, score)| {
-      let count = sample_freq_table.get(k).unwrap_or(&0);
-      score * count
-    })
-    .sum::<u64>()
-    * 10000
-    / total_points
-}
-
-fn trigram_frequency_score(freq_table: &HashMap<String, u64>, sample: String) -> u64 {
-  let total_points: u64 = freq_table.iter().map(|(_, v)| v).sum();
-  sample
-    .to_ascii_uppercase()
-    .chars()
-    .tuple_windows::<(char, char, char)>()
-    .map(|v| vec![v.0, v.1, v.2].iter().collect::<String>())
-    .map(|v| freq_table.get(&v).unwrap_or(&0u64))
-    .sum::<u64>()
-    * 10000
-    / total_points
-}
-
-fn digram_frequency_score(freq_table: &HashMap<String, u64>, sample: String) -> u64 {
-  let total_points: u64 = freq_table.iter().map(|(_, v)| v).sum();
-  sample
-    .to_ascii_uppercase()
-    .chars()
-    .tuple_windows::<(char, char)>()
-    .map(|v| vec![v.0, v.1].iter().collect::<String>())
-    .map(|v| freq_table.get(&v).unwrap_or(&0u64))
-    .sum::<u64>()
-    * 10000
-    / total_points
-}
-
-fn trigram_frequency_table() -> HashMap<String, u64> {
-  let english_trigrams_file = "./res/english_trigrams.txt";
-  BufReader::new(
-    File::open(english_trigrams_file).expect("Failed to open english trigrams freq table file"),
-  )
-  .lines()
-  .map(|v| v.expect("Failed to read line from english trigrams freq table file"))
-  .map(|v| (v.split_at(3).0.to_owned(), v.split_at(4).1.to_owned()))
-  .map(|(v1, v2)| (v1, u64::from_str_radix(&v2, 10).unwrap()))
-  .collect::<HashMap<String, u64>>()
-}
-
-fn digram_frequency_table() -> HashMap<String, u64> {
-  let english_trigrams_file = "./res/english_digrams.txt";
-  BufReader::new(
-    File::open(english_trigrams_file).expect("Failed to open english digrams freq table file"),
-  )
-  .lines()
-  .map(|v| v.expect("Failed to read line from english trigrams freq table file"))
-  .map(|v| (v.split_at(2).0.to_owned(), v.split_at(3).1.to_owned()))
-  .map(|(v1, v2)| (v1, u64::from_str_radix(&v2, 10).unwrap()))
-  .collect::<HashMap<String, u64>>()
-}
+use std::io::stdout;
+
+use cryptopals_challeges_rs::crib_drag::{self, CribSession};
+use cryptopals_challeges_rs::scoring::{ChiSquared, NgramLogLikelihood};
+use cryptopals_challeges_rs::xor_attack::{
+  Charset, KeysizeEstimator, break_fixed_nonce_ctr, break_repeating_xor, crack_single_byte_xor_with,
+  detect_single_byte_xor,
+};
+
+use cryptopals_challeges_rs::cryptog;
+use cryptopals_challeges_rs::oracle;
+use cryptopals_challeges_rs::oracle::oracle_create_token;
+use cryptopals_challeges_rs::oracle::oracle_parse_token;
+use cryptopals_challeges_rs::oracle_hacker;
+use cryptopals_challeges_rs::oracle_hacker::detect_ecb;
+use cryptopals_challeges_rs::xor;
+
+use cryptopals_challeges_rs::htb64;
+use cryptopals_challeges_rs::htb64::base64;
 
 pub fn challenge_1() {
   let input = "49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d";
@@ -132,8 +37,8 @@ pub fn challenge_2() {
   let input1 = "1c0111001f010100061a024b53535009181c";
   let input2 = "686974207468652062756c6c277320657965";
   let res = xor::xor_fixed_length(
-    &htb64::hex_bytes_to_bytes(&input1.as_bytes()).unwrap(),
-    &htb64::hex_bytes_to_bytes(&input2.as_bytes()).unwrap(),
+    &htb64::hex_bytes_to_bytes(input1.as_bytes()).unwrap(),
+    &htb64::hex_bytes_to_bytes(input2.as_bytes()).unwrap(),
   );
   println!("Challenge: fixed/same size xor");
   println!("Input1: {:?}", input1);
@@ -145,29 +50,16 @@ pub fn challenge_2() {
 #[allow(dead_code)]
 pub fn challenge_3() {
   let input = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736".to_owned();
-  let chars = "abcdefghijklmnopqrstuvewxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~".to_owned();
-  let valid = "abcdefghijklmnopqrstuvewxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~";
   let cipher = htb64::hex_bytes_to_bytes(input.as_bytes()).unwrap();
-  let mut table: BTreeMap<u8, u64> = BTreeMap::new();
-  for c in chars.as_bytes() {
-    let attempt = String::from_utf8(xor::xor_single_byte(&cipher, *c));
-    if let Ok(s) = attempt {
-      if s.chars().any(|ch| !valid.contains(ch)) {
-        continue;
-      }
-      table.insert(*c, character_frequency_score(s));
-    }
-  }
-  let mut res = Vec::from_iter(table);
-  res.sort_by(|&(_, v1), &(_, v2)| v1.cmp(&v2).reverse());
+  let res = crack_single_byte_xor_with(&cipher, &ChiSquared, &Charset::printable());
 
   println!("Challenge: Crack Single-byte XOR cipher");
   println!("Input: {:?}", input);
   println!("Attempts:");
-  for (k, v) in res {
+  for candidate in res.iter().take(5) {
     println!("{}", "-".repeat(16));
-    println!("Character: {:?} | Score:{:.5}", String::from_utf8(vec![k]).unwrap(), v);
-    println!("Attempt: {:?}", String::from_utf8(xor::xor_single_byte(&cipher, k)).unwrap())
+    println!("Character: {:?} | Score:{:.5}", candidate.key as char, candidate.score);
+    println!("Attempt: {:?}", String::from_utf8_lossy(&candidate.plaintext))
   }
 }
 
@@ -175,53 +67,33 @@ pub fn challenge_3() {
 pub fn challenge_4() {
   let input_file = "./res/challenge_4_extended.txt";
   // let input_file = "./res/challenge_4.txt";
-  let chars = "abcdefghijklmnopqrstuvewxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~\n\r".to_owned();
-  let valid = "abcdefghijklmnopqrstuvewxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~\n\r";
-  let mut candidates_table: BTreeMap<(usize, String), Vec<(u8, u64, String)>> = BTreeMap::new();
-  let reader =
+  let lines: Vec<Vec<u8>> =
     BufReader::new(File::open(input_file).expect("Failed to open challenge 4 set 01 input file."))
       .lines()
-      .enumerate();
-
-  for (idx, l) in reader {
-    let line = l.expect("Failed to read line from challenge 4 set 01 input file.");
-    let cipher_text =
-      htb64::hex_bytes_to_bytes(line.as_bytes()).expect("Invalid Hexadecimal string");
-    let mut valid_attempts: Vec<(u8, u64, String)> = Vec::new();
-    for c in chars.as_bytes() {
-      let attempt = String::from_utf8(xor::xor_single_byte(&cipher_text, *c));
-      if let Ok(s) = attempt {
-        if s.chars().any(|v| !valid.contains(v)) {
-          continue;
-        }
-        valid_attempts.push((*c, character_frequency_score(s.clone()), s));
-      }
-    }
-    if !valid_attempts.is_empty() {
-      valid_attempts.sort_by(|&(_, s1, _), &(_, s2, _)| s1.cmp(&s2).reverse());
-      candidates_table.insert((idx, line), valid_attempts);
-    }
-  }
+      .map(|v| v.expect("Failed to read line from challenge 4 set 01 input file."))
+      .map(|v| htb64::hex_bytes_to_bytes(v.as_bytes()).expect("Invalid Hexadecimal string"))
+      .collect();
+  let detected = detect_single_byte_xor(&lines, &ChiSquared, &Charset::printable());
   println!("Challenge: Detect single-character XOR");
   println!("Input: {:?}", input_file);
   println!("Line Candidates:");
-  for ((idx, k), v) in candidates_table {
-    if v[0].1 < 90 {
-      continue;
-    }
+  for (idx, top) in detected.iter().take(3) {
     println!("{}", "-".repeat(16));
     println!(
       "Line Number: {}\nLine hex: '{}'\nTop Score: {:.5}\nTop Plaintext Attempt: {:?}",
-      idx, k, v[0].1, v[0].2
+      idx,
+      htb64::bytes_to_hex(&lines[*idx]).to_ascii_lowercase(),
+      top.score,
+      String::from_utf8_lossy(&top.plaintext)
     );
     println!("Candidates: [");
-    let top_3_candidates = &v[0..(if v.len() <= 3 { v.len() } else { 3 })];
-    for (k, s, p) in top_3_candidates {
+    let candidates = crack_single_byte_xor_with(&lines[*idx], &ChiSquared, &Charset::printable());
+    for candidate in candidates.iter().take(3) {
       println!(
-        "Key: '{}' | Score: {:.5} | Plaintext: {:?}",
-        String::from_utf8(vec![*k]).unwrap(),
-        s,
-        p
+        "Key: {:?} | Score: {:.5} | Plaintext: {:?}",
+        candidate.key as char,
+        candidate.score,
+        String::from_utf8_lossy(&candidate.plaintext)
       );
     }
     println!("]");
@@ -244,105 +116,26 @@ I go crazy when I hear a cymbal";
 #[allow(dead_code)]
 pub fn challenge_6() {
   let input_file = "./res/challenge_6.txt";
-  let chars = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~\n\r".to_owned();
-  let valid_chars = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~\n\r".to_owned();
-
-  let lines =
-    BufReader::new(File::open(input_file).expect("Failed to open input file for challenge 6"))
-      .lines();
-  let base64_ciphertext: Vec<u8> = lines
-    .into_iter()
-    .flat_map(|v| {
-      v.expect("Failed to read line from challenge 6 input file").bytes().collect::<Vec<u8>>()
-    })
-    .collect();
 
-  let ciphertext_bytes = BASE64_STANDARD
-    .decode(base64_ciphertext)
-    .expect("Failed to decode Base64 string from input file for challenge 6");
-  let key_sizes = 2..=40;
-
-  let mut scores: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
-  for ks in key_sizes {
-    let sample: Vec<&[u8]> =
-      ciphertext_bytes.chunks(ks).enumerate().take_while(|v| v.0 < 30).map(|(_, v)| v).collect();
-    let s = sample
-      .chunks(2)
-      .map(|v| {
-        hamming_distance(v[0], v[1])
-          .expect("Failed to calculate hamming distance between two vecs of bytes in challenge 6")
-      })
-      .reduce(|v1, v2| v1 + v2)
-      .unwrap_or_default()
-      / (15 * ks as u32);
-    scores.entry(s).or_default().push(ks);
-  }
-  let top: Vec<usize> = scores.iter().take(5).flat_map(|(_, v)| v).copied().collect();
-  // top 5 scores and their key sizes
-  let mut solutions_table: BTreeMap<usize, (String, String)> = BTreeMap::new();
-  for size in top {
-    let mut cols: Vec<Vec<u8>> = vec![vec![]; size];
-    for idx in 0..ciphertext_bytes.len() {
-      cols[idx % size].push(ciphertext_bytes[idx]);
-    }
-    let mut key: Vec<u8> = vec![];
-    for column in cols {
-      let mut char_scores: Vec<(u64, u8)> = Vec::new();
-      for c in chars.as_bytes() {
-        let attempt = String::from_utf8(xor::xor_single_byte(&column, *c));
-        if let Ok(s) = attempt {
-          if s.chars().any(|v| !valid_chars.contains(v)) {
-            continue;
-          }
-          char_scores.push((character_frequency_score(s), *c));
-        }
-      }
-      if char_scores.is_empty() {
-        continue;
-      }
-      char_scores.sort_by(|(s1, _), (s2, _)| s1.cmp(s2).reverse());
-      let (_, best) = char_scores[0];
-      key.append(&mut vec![best]);
-    }
-    if !key.is_empty() {
-      let plaintext = String::from_utf8(xor::xor_repeating_key(&key, &ciphertext_bytes)).unwrap();
-      solutions_table.insert(size, (String::from_utf8(key).unwrap(), plaintext));
-    }
-  }
-  let mut best_solution: (u64, usize, (String, String)) = (0, 0, ("".to_owned(), "".to_owned()));
-  for (key_size, (key, plaintext)) in solutions_table {
-    let score = character_frequency_score(plaintext.clone());
-
-    if best_solution.0 < score {
-      best_solution = (score, key_size, (key, plaintext));
-    }
-  }
+  let ciphertext_bytes = htb64::read_encoded(input_file, &base64::STANDARD)
+    .expect("Failed to read Base64 input file for challenge 6");
+  let solutions = break_repeating_xor(&ciphertext_bytes, &ChiSquared, KeysizeEstimator::Hamming);
+  let best_solution = &solutions[0];
   println!("Challenge: Break repeating-key XOR");
   println!("Input: {:?}", input_file);
-  println!("Guess Key size: {:?}", best_solution.1);
-  println!("Guess Key: {:?}", best_solution.2.0);
+  println!("Guess Key size: {:?}", best_solution.keysize);
+  println!("Guess Key: {:?}", String::from_utf8_lossy(&best_solution.key));
   println!("Actual Key size: 29");
   println!("Actual Key: \"Terminator X: Bring the noise\"");
   println!("Plaintext:\n{}", "-".repeat(16));
-  print!("{}", best_solution.2.1);
+  print!("{}", String::from_utf8_lossy(&best_solution.plaintext));
   println!("{}", "-".repeat(16));
 }
 #[allow(dead_code)]
 pub fn challenge_7() {
   let input_file = "./res/challenge_7.txt";
-  let lines =
-    BufReader::new(File::open(input_file).expect("Failed to open input file for challenge 7"))
-      .lines();
-  let base64_ciphertext: Vec<u8> = lines
-    .into_iter()
-    .flat_map(|v| {
-      v.expect("Failed to read line from challenge 7 input file").bytes().collect::<Vec<u8>>()
-    })
-    .collect();
-
-  let ciphertext_bytes = BASE64_STANDARD
-    .decode(base64_ciphertext)
-    .expect("Failed to decode Base64 string from input file for challenge 7");
+  let ciphertext_bytes = htb64::read_encoded(input_file, &base64::STANDARD)
+    .expect("Failed to read Base64 input file for challenge 7");
 
   let plaintext = cryptog::aes_128_ecb_decrypt("YELLOW SUBMARINE".as_bytes(), &ciphertext_bytes)
     .expect("Failed to decrypt input file from challenge 7");
@@ -371,7 +164,7 @@ pub fn challenge_8() {
   for (idx, line_bytes) in lines_bytes.iter().enumerate() {
     let vec_chunks: Vec<&[u8]> = line_bytes.chunks(16).collect();
     let size = vec_chunks.len();
-    let chunks: HashSet<&[u8]> = HashSet::from_iter(vec_chunks.into_iter());
+    let chunks: HashSet<&[u8]> = HashSet::from_iter(vec_chunks);
     if chunks.len() == size {
       continue;
     }
@@ -401,19 +194,8 @@ pub fn challenge_9() {
 #[allow(dead_code)]
 pub fn challenge_10() {
   let input_file = "./res/challenge_10.txt";
-  let lines =
-    BufReader::new(File::open(input_file).expect("Failed to open input file for challenge 10"))
-      .lines();
-  let base64_ciphertext: Vec<u8> = lines
-    .into_iter()
-    .flat_map(|v| {
-      v.expect("Failed to read line from challenge 10 input file").bytes().collect::<Vec<u8>>()
-    })
-    .collect();
-
-  let ciphertext_bytes = BASE64_STANDARD
-    .decode(base64_ciphertext)
-    .expect("Failed to decode Base64 string from input file for challenge 10");
+  let ciphertext_bytes = htb64::read_encoded(input_file, &base64::STANDARD)
+    .expect("Failed to read Base64 input file for challenge 10");
   let key = "YELLOW SUBMARINE";
   let iv = vec![0; 16];
   let plaintext_bytes = cryptog::aes_cbc_decrypt(&iv, key.as_bytes(), &ciphertext_bytes).unwrap();
@@ -581,7 +363,7 @@ pub fn challenge_17() {
 pub fn challenge_18() {
   let input = "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==";
   let input_bytes =
-    BASE64_STANDARD.decode(input).expect("Failed to decode base64 challenge 18 input");
+    base64::STANDARD.decode(input.as_bytes()).expect("Failed to decode base64 challenge 18 input");
   let key = "YELLOW SUBMARINE".as_bytes().to_owned();
   let nonce = 0;
 
@@ -599,76 +381,29 @@ pub fn challenge_19() {
     BufReader::new(File::open(input_file).expect("Failed to open input file for challenge 19"))
       .lines()
       .map(|v| {
-        BASE64_STANDARD
-          .decode(v.expect("Failed to read a line in the input file for challenge 19"))
+        base64::STANDARD
+          .decode(v.expect("Failed to read a line in the input file for challenge 19").as_bytes())
           .expect("Failed to decode a line in the input file for challenge 19")
       })
       .map(|v| cryptog::aes_ctr(nonce, &key, &v))
       .collect();
 
-  let stdin = stdin();
-  println!("Enter a '<number> <String>' as a guess:");
-  for line in stdin.lines() {
-    let l = line.unwrap();
-    if l == "quit" {
-      return;
-    }
-    let (idx_string, guess) = l.split_once(" ").unwrap();
-    let idx_res = idx_string.parse::<usize>();
-    if idx_res.is_err() {
-      println!("{:?}", idx_res.err().unwrap());
-      continue;
-    }
-    let idx = idx_res.unwrap();
-    if idx >= ciphertexts.len() {
-      println!("{} is too large, choose between {} and {} ", idx, 0, ciphertexts.len() - 1);
-      continue;
-    }
-    let ciphertext = ciphertexts[idx].clone();
-    let keystream =
-      xor::xor_repeating_key(guess.as_bytes(), &ciphertext[0..guess.as_bytes().len()]);
-
-    let transformed: Vec<Result<String,FromUtf8Error>> = ciphertexts.clone()
-      .into_iter()
-      .map(|v| {
-        if v.len() < keystream.len() {
-          (v, vec![])
-        }else{
-          (v[0..keystream.len()].to_vec(), v[keystream.len()..v.len()].to_vec())
-        }
-
-      })
-      .map(|(v1,v2)| (xor::xor_repeating_key(&keystream, &v1),vec![b'?';v2.len()]))
-      .map(|(v1,v2)| {
-        let mut v = vec![];
-        v.append(&mut v1.clone());
-        v.append(&mut v2.clone());
-        String::from_utf8(v)
-      })
-      .collect();
-    if transformed.iter().any(|v| v.is_err()) {
-      println!("Guess created invalid utf8 strings");
-      continue;
-    }
-    for (idx,l) in transformed.iter().map(|v|v.clone().unwrap()).enumerate() {
-      println!("Line {:02}: {:?}",idx,l);
-    }
-  }
+  let mut session = CribSession::new(ciphertexts);
+  println!("Challenge: Break fixed-nonce CTR mode using substitutions");
+  println!("Type 'help' for the commands");
+  crib_drag::run(&mut session, &NgramLogLikelihood::trigrams(), stdin().lock(), stdout())
+    .expect("Failed to read or print a command for challenge 19");
 }
 
 pub fn challenge_20() {
-  let trigram_freq_table = trigram_frequency_table();
-  let digram_freq_table = digram_frequency_table();
-
   let input_file = "res/challenge_20.txt";
-  let valid_chars = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 !@$%^&*()-_+=[{}]'\"\t<>,./?\\|:;`~\n\r".to_owned();
   let lines_bytes: Vec<Vec<u8>> =
     BufReader::new(File::open(input_file).expect("Failed to open input file for challenge 19"))
       .lines()
       .map(|v| v.expect("Failed to read a line in the input file for challenge 19"))
       .map(|v| {
-        BASE64_STANDARD
-          .decode(v)
+        base64::STANDARD
+          .decode(v.as_bytes())
           .expect("Failed to decode a line in the input file for challenge 19")
       })
       .collect();
@@ -678,44 +413,11 @@ pub fn challenge_20() {
   for line_bytes in lines_bytes {
     ciphertexts.push(cryptog::aes_ctr(nonce, &key, &line_bytes));
   }
-  let mut guess_keystream: Vec<u8> = vec![];
-  let mut scores: BTreeMap<u64, u8> = BTreeMap::new();
-  let max_size = ciphertexts.iter().map(|v| v.len()).max().unwrap();
-  for _ in 0..max_size {
-    let mut temp_key = guess_keystream.clone();
-    temp_key.push(0);
-    let idx = temp_key.len() - 1;
-    for byte_value in 0..=255 {
-      temp_key[idx] = byte_value;
-      let guesses: Vec<Result<String, FromUtf8Error>> = ciphertexts
-        .iter()
-        // .filter(|v| idx < v.len())
-        .map(|v| {
-          String::from_utf8(xor::xor_repeating_key(&temp_key, &(*v)[0..=idx.min(v.len() - 1)]))
-        })
-        .collect();
-      if guesses
-        .iter()
-        .any(|v| v.is_err() || v.clone().unwrap().chars().any(|c| !valid_chars.contains(c)))
-      {
-        continue;
-      }
-      let res: String = guesses.iter().map(|v| v.clone().unwrap()).collect();
-      let score = (character_frequency_score(res.clone())
-        + trigram_frequency_score(&trigram_freq_table, res.clone())
-        + digram_frequency_score(&digram_freq_table, res.clone()))
-        * 100
-        / 3;
-      scores.insert(score, byte_value);
-    }
-    let (_, top_guess_byte) = scores.iter().last().unwrap();
-    guess_keystream.push(*top_guess_byte);
-    scores.clear();
-  }
+  let keystream = break_fixed_nonce_ctr(&ciphertexts, &ChiSquared);
   println!("Challenge: Break fixed-nonce CTR statistically");
-  println!("Keystream hex: {:?}", htb64::bytes_to_hex(&guess_keystream.clone()));
+  println!("Keystream hex: {:?}", htb64::bytes_to_hex(&keystream.bytes));
   println!("Plaintext:");
   for ct in ciphertexts {
-    println!("{:?}", String::from_utf8(xor::xor_repeating_key(&guess_keystream, &ct)).unwrap());
+    println!("{:?}", String::from_utf8_lossy(&keystream.decrypt(&ct)));
   }
 }
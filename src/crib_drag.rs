@@ -0,0 +1,518 @@
+use std::{
+  collections::HashMap,
+  fmt::{Display, Write as _},
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Write},
+};
+
+use itertools::Itertools;
+
+use crate::{
+  htb64::hex,
+  scoring::{Scorer, WordDictionary},
+  xor_attack::Charset,
+};
+
+// shown in place of the plaintext bytes under an unknown keystream byte
+const UNKNOWN: char = '·';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CribError {
+  BadCommand(String),
+  LineOutOfRange(usize),
+  ColumnOutOfRange(usize),
+  NoSuchMatch(usize),
+  NothingToUndo,
+}
+
+impl Display for CribError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CribError::BadCommand(msg) => write!(f, "{msg}, type 'help' for the commands"),
+      CribError::LineOutOfRange(line) => write!(f, "There is no line {line}"),
+      CribError::ColumnOutOfRange(column) => write!(f, "Column {column} is out of range"),
+      CribError::NoSuchMatch(idx) => write!(f, "There is no match #{idx} in the last drag"),
+      CribError::NothingToUndo => write!(f, "Nothing to undo"),
+    }
+  }
+}
+
+// a crib placed in one line, with what the keystream it implies does to every other line
+#[derive(Debug, Clone, PartialEq)]
+pub struct CribMatch {
+  pub line: usize,
+  pub offset: usize,
+  pub keystream: Vec<u8>,
+  // (line, decrypted bytes) for the other lines reaching offset
+  pub fragments: Vec<(usize, Vec<u8>)>,
+  pub score: f64,
+}
+
+// a many-time pad being decrypted by hand: ciphertexts under one keystream, of which some bytes
+// are known
+#[derive(Debug, Clone)]
+pub struct CribSession {
+  ciphertexts: Vec<Vec<u8>>,
+  keystream: Vec<Option<u8>>,
+  // keystreams before every change, the latest last
+  history: Vec<Vec<Option<u8>>>,
+  matches: Vec<CribMatch>,
+}
+
+impl CribSession {
+  pub fn new(ciphertexts: Vec<Vec<u8>>) -> CribSession {
+    let len = ciphertexts.iter().map(|v| v.len()).max().unwrap_or_default();
+    CribSession { ciphertexts, keystream: vec![None; len], history: vec![], matches: vec![] }
+  }
+
+  pub fn ciphertexts(&self) -> &[Vec<u8>] {
+    &self.ciphertexts
+  }
+
+  pub fn keystream(&self) -> &[Option<u8>] {
+    &self.keystream
+  }
+
+  // the matches of the last drag, best first
+  pub fn matches(&self) -> &[CribMatch] {
+    &self.matches
+  }
+
+  pub fn decrypt(&self, line: usize) -> Result<Vec<Option<u8>>, CribError> {
+    let ciphertext = self.ciphertexts.get(line).ok_or(CribError::LineOutOfRange(line))?;
+    Ok(ciphertext.iter().zip(&self.keystream).map(|(c, k)| k.map(|k| c ^ k)).collect())
+  }
+
+  // slide crib over every position of every line, keeping the placements that leave every other
+  // line printable
+  pub fn drag(&mut self, crib: &[u8], scorer: &dyn Scorer) -> &[CribMatch] {
+    let printable = Charset::printable();
+    let mut matches = vec![];
+    if crib.is_empty() {
+      self.matches = matches;
+      return &self.matches;
+    }
+    for (line, ciphertext) in self.ciphertexts.iter().enumerate() {
+      for offset in 0..=ciphertext.len().saturating_sub(crib.len()) {
+        let Some(window) = ciphertext.get(offset..offset + crib.len()) else {
+          continue;
+        };
+        let keystream: Vec<u8> = window.iter().zip(crib).map(|(c, p)| c ^ p).collect();
+        let fragments: Vec<(usize, Vec<u8>)> = self
+          .ciphertexts
+          .iter()
+          .enumerate()
+          .filter(|(other, v)| *other != line && v.len() > offset)
+          .map(|(other, v)| {
+            (other, v[offset..].iter().zip(&keystream).map(|(c, k)| c ^ k).collect())
+          })
+          .collect();
+        if fragments.iter().any(|(_, v)| !printable.accepts(v)) {
+          continue;
+        }
+        let sample = fragments.iter().map(|(_, v)| v.as_slice()).collect_vec().join(&b'\n');
+        let score = scorer.score(&sample);
+        matches.push(CribMatch { line, offset, keystream, fragments, score });
+      }
+    }
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    self.matches = matches;
+    &self.matches
+  }
+
+  // take the keystream of a match of the last drag
+  pub fn accept(&mut self, idx: usize) -> Result<(), CribError> {
+    let found = self.matches.get(idx).ok_or(CribError::NoSuchMatch(idx))?;
+    let (offset, keystream) = (found.offset, found.keystream.clone());
+    self.set_range(offset, &keystream)
+  }
+
+  // plaintext known at offset in line, it has to fit in the line
+  pub fn guess(&mut self, line: usize, offset: usize, plaintext: &[u8]) -> Result<(), CribError> {
+    let ciphertext = self.ciphertexts.get(line).ok_or(CribError::LineOutOfRange(line))?;
+    if offset >= ciphertext.len() {
+      return Err(CribError::ColumnOutOfRange(offset));
+    }
+    if offset + plaintext.len() > ciphertext.len() {
+      return Err(CribError::ColumnOutOfRange(ciphertext.len()));
+    }
+    let keystream: Vec<u8> =
+      ciphertext[offset..].iter().zip(plaintext).map(|(c, p)| c ^ p).collect();
+    self.set_range(offset, &keystream)
+  }
+
+  pub fn set(&mut self, column: usize, byte: u8) -> Result<(), CribError> {
+    self.set_range(column, &[byte])
+  }
+
+  // forget the keystream bytes of columns
+  pub fn revert(&mut self, columns: std::ops::Range<usize>) -> Result<(), CribError> {
+    if columns.is_empty() {
+      return Err(CribError::BadCommand("Empty column range".to_owned()));
+    }
+    if columns.end > self.keystream.len() {
+      return Err(CribError::ColumnOutOfRange(columns.end - 1));
+    }
+    self.history.push(self.keystream.clone());
+    self.keystream[columns].fill(None);
+    Ok(())
+  }
+
+  // fill the unknown columns from an automatic crib drag
+  pub fn auto(&mut self, cribs: &[Vec<u8>], scorer: &dyn Scorer) {
+    let found = auto_crib_drag(&self.ciphertexts, cribs, scorer);
+    self.history.push(self.keystream.clone());
+    self.keystream.iter_mut().zip(found.keystream).for_each(|(k, v)| *k = k.or(v));
+  }
+
+  pub fn undo(&mut self) -> Result<(), CribError> {
+    self.keystream = self.history.pop().ok_or(CribError::NothingToUndo)?;
+    Ok(())
+  }
+
+  fn set_range(&mut self, offset: usize, bytes: &[u8]) -> Result<(), CribError> {
+    if offset + bytes.len() > self.keystream.len() {
+      return Err(CribError::ColumnOutOfRange(offset + bytes.len() - 1));
+    }
+    self.history.push(self.keystream.clone());
+    self.keystream[offset..offset + bytes.len()]
+      .iter_mut()
+      .zip(bytes)
+      .for_each(|(k, b)| *k = Some(*b));
+    Ok(())
+  }
+
+  // every line decrypted as far as the keystream is known, under a ruler of column numbers
+  pub fn render(&self) -> String {
+    let mut res = String::new();
+    let ruler: String = (0..self.keystream.len()).step_by(10).map(|v| format!("{v:<10}")).collect();
+    writeln!(res, "     {}", ruler.trim_end()).unwrap();
+    for line in 0..self.ciphertexts.len() {
+      writeln!(res, "{line:02} | {}", escape(&self.decrypt(line).unwrap())).unwrap();
+    }
+    res
+  }
+
+  // the ciphertexts, then the keystream with ?? for unknown bytes, all in hex
+  pub fn save(&self, path: &str) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "{}", self.ciphertexts.iter().map(hex::encode).join(" "))?;
+    let keystream =
+      self.keystream.iter().map(|v| v.map_or("??".to_owned(), |v| format!("{v:02x}")));
+    writeln!(file, "{}", keystream.collect::<String>())?;
+    file.flush()
+  }
+
+  pub fn load(path: &str) -> io::Result<CribSession> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+    let lines: Vec<String> = BufReader::new(File::open(path)?).lines().collect::<Result<_, _>>()?;
+    let [ciphertexts, keystream] = &lines[..] else {
+      return Err(invalid("A session has exactly two lines"));
+    };
+    let ciphertexts = ciphertexts
+      .split_whitespace()
+      .map(|v| hex::decode(v.as_bytes()))
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|_| invalid("Invalid ciphertext"))?;
+    let keystream = keystream
+      .as_bytes()
+      .chunks(2)
+      .map(|v| match v {
+        b"??" => Ok(None),
+        v => Ok(Some(hex::decode(v).map_err(|_| invalid("Invalid keystream"))?[0])),
+      })
+      .collect::<io::Result<Vec<_>>>()?;
+    let mut res = CribSession::new(ciphertexts);
+    if keystream.len() != res.keystream.len() {
+      return Err(invalid("The keystream doesn't match the ciphertexts"));
+    }
+    res.keystream = keystream;
+    Ok(res)
+  }
+}
+
+// printable ASCII as is, backslash doubled, other bytes as \xNN and unknown bytes marked
+pub fn escape(bytes: &[Option<u8>]) -> String {
+  bytes
+    .iter()
+    .map(|v| match v {
+      None => UNKNOWN.to_string(),
+      Some(b'\\') => "\\\\".to_owned(),
+      Some(v) if *v == b' ' || v.is_ascii_graphic() => (*v as char).to_string(),
+      Some(v) => format!("\\x{v:02x}"),
+    })
+    .collect()
+}
+
+const HELP: &str = "\
+drag <crib>                  slide crib over every line and list where it fits
+auto                         fill the unknown columns from dictionary cribs
+accept <n>                   take the keystream of match n of the last drag
+guess <line> <offset> <text> the text is in line at offset
+set <column> <hex byte>      set one keystream byte
+revert <column>[..<end>]     forget the keystream of a column or a range
+undo                         undo the last change
+show                         print the grid
+save <file> / load <file>    write or read the session
+quit";
+
+// read commands from input until quit or the end of input, printing to output
+pub fn run(
+  session: &mut CribSession,
+  scorer: &dyn Scorer,
+  input: impl BufRead,
+  mut output: impl Write,
+) -> io::Result<()> {
+  write!(output, "{}", session.render())?;
+  for line in input.lines() {
+    let line = line?;
+    let (command, args) = line.trim_start().split_once(' ').unwrap_or((line.trim(), ""));
+    let res = match command {
+      "" => continue,
+      "quit" => return Ok(()),
+      "help" => {
+        writeln!(output, "{HELP}")?;
+        continue;
+      }
+      "drag" => {
+        let matches = session.drag(args.as_bytes(), scorer);
+        for (idx, found) in matches.iter().enumerate().take(10) {
+          let fragments = found
+            .fragments
+            .iter()
+            .map(|(line, v)| {
+              format!("{line:02}:{}", escape(&v.iter().map(|v| Some(*v)).collect_vec()))
+            })
+            .join(" | ");
+          writeln!(output, "#{idx} line {} offset {}: {}", found.line, found.offset, fragments)?;
+        }
+        writeln!(output, "{} matches", matches.len())?;
+        continue;
+      }
+      "show" => Ok(()),
+      "accept" => parse(args).and_then(|idx| session.accept(idx)),
+      "guess" => match args.splitn(3, ' ').collect_vec()[..] {
+        [line, offset, text] => parse(line)
+          .and_then(|line| Ok((line, parse(offset)?)))
+          .and_then(|(line, offset)| session.guess(line, offset, text.as_bytes())),
+        _ => Err(CribError::BadCommand("Usage: guess <line> <offset> <text>".to_owned())),
+      },
+      "set" => match args.split_whitespace().collect_vec()[..] {
+        [column, byte] => parse(column).and_then(|column| {
+          let byte = u8::from_str_radix(byte, 16)
+            .map_err(|_| CribError::BadCommand(format!("Invalid byte '{byte}'")))?;
+          session.set(column, byte)
+        }),
+        _ => Err(CribError::BadCommand("Usage: set <column> <hex byte>".to_owned())),
+      },
+      "revert" => match args.split_once("..") {
+        Some((start, end)) => parse(start)
+          .and_then(|start| Ok(start..parse(end)?))
+          .and_then(|columns| session.revert(columns)),
+        None => parse(args).and_then(|column| session.revert(column..column + 1)),
+      },
+      "auto" => {
+        session.auto(&cribs(&WordDictionary::english(), 3), scorer);
+        Ok(())
+      }
+      "undo" => session.undo(),
+      "save" => {
+        match session.save(args.trim()) {
+          Ok(()) => writeln!(output, "Saved to {}", args.trim())?,
+          Err(err) => writeln!(output, "Failed to save: {err}")?,
+        }
+        continue;
+      }
+      "load" => {
+        match CribSession::load(args.trim()) {
+          Ok(loaded) => *session = loaded,
+          Err(err) => {
+            writeln!(output, "Failed to load: {err}")?;
+            continue;
+          }
+        }
+        Ok(())
+      }
+      other => Err(CribError::BadCommand(format!("Unknown command '{other}'"))),
+    };
+    match res {
+      Ok(()) => write!(output, "{}", session.render())?,
+      Err(err) => writeln!(output, "{err}")?,
+    }
+  }
+  Ok(())
+}
+
+fn parse(arg: &str) -> Result<usize, CribError> {
+  arg.trim().parse().map_err(|_| CribError::BadCommand(format!("Invalid number '{}'", arg.trim())))
+}
+
+// lines besides the crib's own that must reach a placement
+const MIN_FRAGMENTS: usize = 3;
+
+// a crib found at offset in line
+#[derive(Debug, Clone, PartialEq)]
+pub struct CribPlacement {
+  pub line: usize,
+  pub offset: usize,
+  pub crib: Vec<u8>,
+  // score of what the crib decrypts the other lines to
+  pub score: f64,
+}
+
+// the keystream rebuilt from cribs without help, None where nothing was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconstruction {
+  pub keystream: Vec<Option<u8>>,
+  // the placements the keystream was built from, best first
+  pub placements: Vec<CribPlacement>,
+}
+
+impl Reconstruction {
+  pub fn decrypt(&self, ciphertext: &[u8]) -> Vec<Option<u8>> {
+    ciphertext.iter().zip(&self.keystream).map(|(c, k)| k.map(|k| c ^ k)).collect()
+  }
+
+  pub fn known(&self) -> usize {
+    self.keystream.iter().filter(|v| v.is_some()).count()
+  }
+}
+
+// " word " and "Word " for every dictionary word of at least min_len letters
+pub fn cribs(dictionary: &WordDictionary, min_len: usize) -> Vec<Vec<u8>> {
+  let mut res = vec![];
+  for word in dictionary.words().into_iter().filter(|v| v.len() >= min_len) {
+    let lower = word.to_ascii_lowercase();
+    res.push([b" ", lower.as_slice(), b" "].concat());
+    res.push([&word[..1], &lower[1..], b" "].concat());
+  }
+  res
+}
+
+// every placement of every crib in every line that decrypts the other lines, through the XOR of
+// the two ciphertexts, to prose. Best first
+pub fn crib_placements<T: AsRef<[u8]>, C: AsRef<[u8]>>(
+  ciphertexts: &[T],
+  cribs: &[C],
+  scorer: &dyn Scorer,
+) -> Vec<CribPlacement> {
+  let prose = Charset::prose();
+  let ciphertexts: Vec<&[u8]> = ciphertexts.iter().map(|v| v.as_ref()).collect();
+  // pairwise[i][j] is the XOR of lines i and j, which is also the XOR of their plaintexts
+  let pairwise: Vec<Vec<Vec<u8>>> = ciphertexts
+    .iter()
+    .map(|a| ciphertexts.iter().map(|b| a.iter().zip(*b).map(|(a, b)| a ^ b).collect()).collect())
+    .collect();
+  let mut res = vec![];
+  for (line, ciphertext) in ciphertexts.iter().enumerate() {
+    for crib in cribs.iter().map(|v| v.as_ref()) {
+      for offset in 0..(ciphertext.len() + 1).saturating_sub(crib.len()) {
+        let fragments: Option<Vec<Vec<u8>>> = (0..ciphertexts.len())
+          .filter(|other| *other != line && pairwise[line][*other].len() > offset)
+          .map(|other| {
+            let fragment: Vec<u8> =
+              pairwise[line][other][offset..].iter().zip(crib).map(|(x, p)| x ^ p).collect();
+            prose.accepts(&fragment).then_some(fragment)
+          })
+          .collect();
+        let Some(fragments) = fragments.filter(|v| v.len() >= MIN_FRAGMENTS) else {
+          continue;
+        };
+        let score = scorer.score(&fragments.join(&b'\n'));
+        res.push(CribPlacement { line, offset, crib: crib.to_owned(), score });
+      }
+    }
+  }
+  res.sort_by(|a, b| b.score.total_cmp(&a.score));
+  res
+}
+
+// seed the keystream with the crib placements, every column taking the byte most placements
+// agree on, then greedily extend it into the unknown columns next to known ones
+pub fn auto_crib_drag<T: AsRef<[u8]>, C: AsRef<[u8]>>(
+  ciphertexts: &[T],
+  cribs: &[C],
+  scorer: &dyn Scorer,
+) -> Reconstruction {
+  let ciphertexts: Vec<&[u8]> = ciphertexts.iter().map(|v| v.as_ref()).collect();
+  let len = ciphertexts.iter().map(|v| v.len()).max().unwrap_or_default();
+  let placements = crib_placements(&ciphertexts, cribs, scorer);
+  // (votes, best score) for every byte of every column
+  let mut votes: Vec<HashMap<u8, (usize, f64)>> = vec![HashMap::new(); len];
+  for placement in &placements {
+    let ciphertext = &ciphertexts[placement.line][placement.offset..];
+    for (column, (c, p)) in ciphertext.iter().zip(&placement.crib).enumerate() {
+      let vote = votes[placement.offset + column].entry(c ^ p).or_insert((0, placement.score));
+      vote.0 += 1;
+    }
+  }
+  let mut keystream: Vec<Option<u8>> = votes
+    .iter()
+    .map(|v| {
+      v.iter().max_by(|(_, a), (_, b)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))).map(|(k, _)| *k)
+    })
+    .collect();
+  while extend(&ciphertexts, &mut keystream, scorer) {}
+  let placements = placements
+    .into_iter()
+    .filter(|v| {
+      let ciphertext = &ciphertexts[v.line][v.offset..];
+      ciphertext
+        .iter()
+        .zip(&v.crib)
+        .zip(&keystream[v.offset..])
+        .all(|((c, p), k)| *k == Some(c ^ p))
+    })
+    .collect();
+  Reconstruction { keystream, placements }
+}
+
+// guess every unknown column next to a known one from the few known plaintext bytes beside it,
+// false once nothing more can be guessed
+fn extend(ciphertexts: &[&[u8]], keystream: &mut [Option<u8>], scorer: &dyn Scorer) -> bool {
+  let printable = Charset::printable();
+  let mut extended = false;
+  for column in 0..keystream.len() {
+    if keystream[column].is_some() {
+      continue;
+    }
+    // the known bytes before the column, else the ones after it
+    let context = if column > 0 && keystream[column - 1].is_some() {
+      column.saturating_sub(3)..column
+    } else if column + 1 < keystream.len() && keystream[column + 1].is_some() {
+      column + 1..(column + 4).min(keystream.len())
+    } else {
+      continue;
+    };
+    let reaching: Vec<&[u8]> = ciphertexts.iter().copied().filter(|v| v.len() > column).collect();
+    let best = (0..=255u8)
+      .filter_map(|k| {
+        let mut sample = vec![];
+        for ciphertext in &reaching {
+          let byte = ciphertext[column] ^ k;
+          if !printable.contains(byte) {
+            return None;
+          }
+          let known = ciphertext.iter().zip(keystream.iter()).map(|(c, s)| s.map(|s| c ^ s));
+          if context.start > column {
+            sample.push(byte);
+            sample.extend(known.skip(context.start).take(context.len()).map_while(|v| v));
+          } else {
+            let mut before: Vec<u8> =
+              known.take(context.end).skip(context.start).rev().map_while(|v| v).collect();
+            before.reverse();
+            sample.extend(before);
+            sample.push(byte);
+          }
+          sample.push(b'\n');
+        }
+        Some((scorer.score(&sample), k))
+      })
+      .max_by(|(a, _), (b, _)| a.total_cmp(b));
+    if let Some((_, k)) = best {
+      keystream[column] = Some(k);
+      extended = true;
+    }
+  }
+  extended
+}
@@ -2,7 +2,11 @@ use std::iter;
 
 use byteorder::{ByteOrder, LittleEndian};
 use openssl::{
-  error::ErrorStack, symm::{Cipher, Crypter}
+  error::ErrorStack,
+  hash::MessageDigest,
+  pkey::PKey,
+  sign::Signer,
+  symm::{Cipher, Crypter},
 };
 
 use crate::xor::{self, xor_fixed_length};
@@ -121,5 +125,47 @@ pub fn aes_ctr(nonce: u64, key: &[u8], input: &[u8])  -> Vec<u8> {
     Some(next)
   });
 
-  input.chunks(key.len()).map(|v| xor::xor_repeating_key(&key_stream.next().unwrap(), v)).flatten().collect()
+  input.chunks(key.len()).flat_map(|v| xor::xor_repeating_key(&key_stream.next().unwrap(), v)).collect()
+}
+
+pub fn rc4(key: &[u8], input: &[u8]) -> Vec<u8> {
+  assert!(!key.is_empty(), "RC4 keys are 1 to 256 bytes long");
+  let mut state: [u8; 256] = std::array::from_fn(|v| v as u8);
+  let mut j = 0u8;
+  for i in 0..256 {
+    j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+    state.swap(i, j as usize);
+  }
+  let (mut i, mut j) = (0u8, 0u8);
+  input
+    .iter()
+    .map(|v| {
+      i = i.wrapping_add(1);
+      j = j.wrapping_add(state[i as usize]);
+      state.swap(i as usize, j as usize);
+      v ^ state[state[i as usize].wrapping_add(state[j as usize]) as usize]
+    })
+    .collect()
+}
+
+// last block of the CBC encryption, aes_cbc_encrypt already takes care of the padding
+pub fn cbc_mac(iv: &[u8], key: &[u8], message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  let ciphertext = aes_cbc_encrypt(iv, key, message)?;
+  Ok(ciphertext[ciphertext.len() - key.len()..].to_owned())
+}
+
+pub fn cbc_mac_zero_iv(key: &[u8], message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  cbc_mac(&vec![0; key.len()], key, message)
+}
+
+// CBC-MAC with a public key and zero IV used as a (broken) hash function
+pub fn cbc_mac_hash(message: &[u8]) -> Vec<u8> {
+  cbc_mac_zero_iv(b"YELLOW SUBMARINE", message).unwrap()
+}
+
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  let key = PKey::hmac(key)?;
+  let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+  signer.update(message)?;
+  signer.sign_to_vec()
 }
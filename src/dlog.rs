@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use openssl::{
+  bn::{BigNum, BigNumContext, BigNumRef},
+  error::ErrorStack,
+};
+
+use crate::{
+  bignum::{self, mod_exp, mod_inverse, mod_mul},
+  ec::{EcPoint, WeierstrassCurve},
+};
+
+const CRYPTOPALS_P: &str = "7199773997391911030609999317773941274322764333428698921736339643928346453700085358802973900485592910475480089726140708102474957429903531369589969318716771";
+const CRYPTOPALS_G: &str = "4565356397095740655436854503483826832136106141639563487732438195343690437606117828318042418238184896212352329118608100083187535033402010599512641674644143";
+const CRYPTOPALS_Q: &str = "236234353446506858198510045061214171961";
+
+// a finite cyclic group written multiplicatively, elements need not be Clone so that BigNum fits
+pub trait Group {
+  type Element: PartialEq;
+
+  fn identity(&self) -> Result<Self::Element, ErrorStack>;
+
+  fn op(&self, a: &Self::Element, b: &Self::Element) -> Result<Self::Element, ErrorStack>;
+
+  fn inverse(&self, a: &Self::Element) -> Result<Self::Element, ErrorStack>;
+
+  fn pow(&self, a: &Self::Element, k: &BigNumRef) -> Result<Self::Element, ErrorStack>;
+
+  // canonical bytes of an element, used as a hash key
+  fn encode(&self, a: &Self::Element) -> Vec<u8>;
+
+  fn copy(&self, a: &Self::Element) -> Result<Self::Element, ErrorStack> {
+    self.op(a, &self.identity()?)
+  }
+}
+
+// the multiplicative group modulo a prime
+#[derive(Debug)]
+pub struct MultiplicativeGroup {
+  pub p: BigNum,
+}
+
+impl Group for MultiplicativeGroup {
+  type Element = BigNum;
+
+  fn identity(&self) -> Result<BigNum, ErrorStack> {
+    BigNum::from_u32(1)
+  }
+
+  fn op(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_mul(a, b, &self.p)
+  }
+
+  fn inverse(&self, a: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_inverse(a, &self.p)
+  }
+
+  fn pow(&self, a: &BigNum, k: &BigNumRef) -> Result<BigNum, ErrorStack> {
+    mod_exp(a, k, &self.p)
+  }
+
+  fn encode(&self, a: &BigNum) -> Vec<u8> {
+    a.to_vec()
+  }
+}
+
+impl Group for WeierstrassCurve {
+  type Element = EcPoint;
+
+  fn identity(&self) -> Result<EcPoint, ErrorStack> {
+    Ok(EcPoint::Infinity)
+  }
+
+  fn op(&self, a: &EcPoint, b: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    self.add(a, b)
+  }
+
+  fn inverse(&self, a: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    self.neg(a)
+  }
+
+  fn pow(&self, a: &EcPoint, k: &BigNumRef) -> Result<EcPoint, ErrorStack> {
+    self.scalar_mul(k, a)
+  }
+
+  // fixed width x || y, empty for the point at infinity
+  fn encode(&self, a: &EcPoint) -> Vec<u8> {
+    match a {
+      EcPoint::Infinity => vec![],
+      EcPoint::Affine { x, y } => {
+        let width = self.p.num_bytes();
+        let mut res = x.to_vec_padded(width).unwrap();
+        res.extend(y.to_vec_padded(width).unwrap());
+        res
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct DhParams {
+  pub p: BigNum,
+  pub g: BigNum,
+  // order of g, a small factor of p - 1
+  pub q: BigNum,
+}
+
+pub fn cryptopals_params() -> Result<DhParams, ErrorStack> {
+  Ok(DhParams {
+    p: BigNum::from_dec_str(CRYPTOPALS_P)?,
+    g: BigNum::from_dec_str(CRYPTOPALS_G)?,
+    q: BigNum::from_dec_str(CRYPTOPALS_Q)?,
+  })
+}
+
+// (secret, public)
+pub fn generate_keypair(params: &DhParams) -> Result<(BigNum, BigNum), ErrorStack> {
+  let mut secret = bignum::random_below(&params.q)?;
+  while secret.num_bits() == 0 {
+    secret = bignum::random_below(&params.q)?;
+  }
+  let public = mod_exp(&params.g, &secret, &params.p)?;
+  Ok((secret, public))
+}
+
+// baby-step giant-step: the k in [0, bound) with generator^k = target. Bounds past 64 bits are
+// out of reach anyway and give None
+pub fn baby_step_giant_step<G: Group>(
+  group: &G,
+  generator: &G::Element,
+  target: &G::Element,
+  bound: &BigNumRef,
+) -> Result<Option<BigNum>, ErrorStack> {
+  let Some(bound) = bignum::to_u64(bound) else {
+    return Ok(None);
+  };
+  let m = bound.isqrt() + 1;
+  let mut baby_steps = HashMap::new();
+  let mut baby = group.identity()?;
+  for j in 0..m {
+    baby_steps.entry(group.encode(&baby)).or_insert(j);
+    baby = group.op(&baby, generator)?;
+  }
+  // target * generator^(-m i) = generator^j
+  let m_bn = bignum::from_u64(m)?;
+  let giant_step = group.inverse(&group.pow(generator, &m_bn)?)?;
+  let mut giant = group.copy(target)?;
+  for i in 0..m {
+    if let Some(j) = baby_steps.get(&group.encode(&giant)) {
+      let k = i * m + j;
+      return (k < bound).then(|| bignum::from_u64(k)).transpose();
+    }
+    giant = group.op(&giant, &giant_step)?;
+  }
+  Ok(None)
+}
+
+// Pohlig-Hellman: the discrete log modulo the order of generator, given that order factored as
+// (prime, exponent) pairs. Every prime power subgroup is solved with baby-step giant-step
+pub fn pohlig_hellman<G: Group>(
+  group: &G,
+  generator: &G::Element,
+  target: &G::Element,
+  factors: &[(u32, u32)],
+) -> Result<Option<BigNum>, ErrorStack> {
+  let mut ctx = BigNumContext::new()?;
+  let mut powers = vec![];
+  for (r, e) in factors {
+    let (r, e) = (BigNum::from_u32(*r)?, BigNum::from_u32(*e)?);
+    let mut power = BigNum::new()?;
+    power.exp(&r, &e, &mut ctx)?;
+    powers.push(power);
+  }
+  let mut order = BigNum::from_u32(1)?;
+  for power in &powers {
+    order = &order * power;
+  }
+  let mut residues = vec![];
+  for power in powers {
+    let cofactor = &order / &power;
+    let sub_generator = group.pow(generator, &cofactor)?;
+    let sub_target = group.pow(target, &cofactor)?;
+    let Some(x) = baby_step_giant_step(group, &sub_generator, &sub_target, &power)? else {
+      return Ok(None);
+    };
+    residues.push((x, power));
+  }
+  Ok(Some(bignum::crt(&residues)?.0))
+}
+
+// Pollard's kangaroo: the k in [lower, upper] with generator^k = target. Ranges past 64 bits are
+// out of reach anyway and give None
+pub fn kangaroo<G: Group>(
+  group: &G,
+  generator: &G::Element,
+  target: &G::Element,
+  lower: &BigNumRef,
+  upper: &BigNumRef,
+) -> Result<Option<BigNum>, ErrorStack> {
+  let range = upper - lower;
+  let Some(range) = bignum::to_u64(&range) else {
+    return Ok(None);
+  };
+  // jumps are powers of two up to about half the square root of the range
+  let half_bits = range.max(1).ilog2().div_ceil(2).max(1);
+  let k = (half_bits + half_bits.ilog2()).saturating_sub(2).max(1);
+  let jumps: Vec<u64> = (0..k).map(|i| 1 << i).collect();
+  let mut jump_elements = vec![];
+  for jump in &jumps {
+    let jump = bignum::from_u64(*jump)?;
+    jump_elements.push(group.pow(generator, &jump)?);
+  }
+  let index = |element: &G::Element| {
+    let hash = group.encode(element).iter().fold(0u64, |acc, v| acc.wrapping_mul(31) ^ *v as u64);
+    (hash % k as u64) as usize
+  };
+  let steps = 4 * jumps.iter().sum::<u64>() / k as u64;
+  // the tame kangaroo starts at upper and leaves a trap behind
+  let mut tame = group.pow(generator, upper)?;
+  let mut tame_distance = 0u64;
+  for _ in 0..steps {
+    let i = index(&tame);
+    tame_distance += jumps[i];
+    tame = group.op(&tame, &jump_elements[i])?;
+  }
+  let mut wild = group.copy(target)?;
+  let mut wild_distance = 0u64;
+  let limit = range.saturating_add(tame_distance);
+  while wild_distance <= limit {
+    if wild == tame {
+      let end = upper + &bignum::from_u64(tame_distance)?;
+      return Ok(Some(&end - &bignum::from_u64(wild_distance)?));
+    }
+    let i = index(&wild);
+    let Some(distance) = wild_distance.checked_add(jumps[i]) else {
+      break;
+    };
+    wild_distance = distance;
+    wild = group.op(&wild, &jump_elements[i])?;
+  }
+  Ok(None)
+}
@@ -0,0 +1,236 @@
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader},
+};
+
+use openssl::{
+  bn::{BigNum, BigNumRef},
+  error::ErrorStack,
+  sha::sha1,
+};
+
+use crate::{
+  bignum::{self, mod_exp, mod_inverse, mod_mul, mod_sub, nnmod},
+  htb64::hex,
+};
+
+const CRYPTOPALS_P: &str = "800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65eac698c1702578b07dc2a1076da241c76c62d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebeac04f48c3c84afb796d61e5a4f9a8fda812ab59494232c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015efc871a584471bb1";
+const CRYPTOPALS_Q: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+const CRYPTOPALS_G: &str = "5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa4046c8db53039db620c094c9fa077ef389b5322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470f5b64c36b625a097f1651fe775323556fe00b3608c887892878480e99041be601a62166ca6894bdd41a7054ec89f756ba9fc95302291";
+
+#[derive(Debug)]
+pub struct DsaParams {
+  pub p: BigNum,
+  pub q: BigNum,
+  pub g: BigNum,
+}
+
+#[derive(Debug)]
+pub struct DsaKeyPair {
+  pub x: BigNum,
+  pub y: BigNum,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DsaSignature {
+  pub r: BigNum,
+  pub s: BigNum,
+}
+
+#[derive(Debug)]
+pub struct SignedMessage {
+  pub message: String,
+  pub hash: BigNum,
+  pub signature: DsaSignature,
+}
+
+pub fn cryptopals_params() -> Result<DsaParams, ErrorStack> {
+  Ok(DsaParams {
+    p: BigNum::from_hex_str(CRYPTOPALS_P)?,
+    q: BigNum::from_hex_str(CRYPTOPALS_Q)?,
+    g: BigNum::from_hex_str(CRYPTOPALS_G)?,
+  })
+}
+
+pub fn hash_message(message: &[u8]) -> Result<BigNum, ErrorStack> {
+  BigNum::from_slice(&sha1(message))
+}
+
+pub fn generate_keypair(params: &DsaParams) -> Result<DsaKeyPair, ErrorStack> {
+  let mut x = bignum::random_below(&params.q)?;
+  while x.num_bits() == 0 {
+    x = bignum::random_below(&params.q)?;
+  }
+  let y = mod_exp(&params.g, &x, &params.p)?;
+  Ok(DsaKeyPair { x, y })
+}
+
+pub fn public_key(params: &DsaParams, x: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  mod_exp(&params.g, x, &params.p)
+}
+
+pub fn sign_with_nonce(
+  params: &DsaParams,
+  x: &BigNumRef,
+  k: &BigNumRef,
+  hash: &BigNumRef,
+) -> Result<DsaSignature, ErrorStack> {
+  let g_k = mod_exp(&params.g, k, &params.p)?;
+  let r = nnmod(&g_k, &params.q)?;
+  let k_inv = mod_inverse(k, &params.q)?;
+  let s = mod_mul(&k_inv, &(hash + &mod_mul(x, &r, &params.q)?), &params.q)?;
+  Ok(DsaSignature { r, s })
+}
+
+// r == 0 is deliberately not rejected so that tampered generators (g = 0) still produce signatures
+pub fn sign(params: &DsaParams, x: &BigNumRef, message: &[u8]) -> Result<DsaSignature, ErrorStack> {
+  let hash = hash_message(message)?;
+  loop {
+    let k = bignum::random_below(&params.q)?;
+    if k.num_bits() == 0 {
+      continue;
+    }
+    let signature = sign_with_nonce(params, x, &k, &hash)?;
+    if signature.s.num_bits() != 0 {
+      return Ok(signature);
+    }
+  }
+}
+
+pub fn verify(
+  params: &DsaParams,
+  y: &BigNumRef,
+  message: &[u8],
+  signature: &DsaSignature,
+) -> Result<bool, ErrorStack> {
+  let in_range = |v: &BigNum| v.num_bits() != 0 && *v < params.q;
+  if !in_range(&signature.r) || !in_range(&signature.s) {
+    return Ok(false);
+  }
+  verify_unchecked(params, y, message, signature)
+}
+
+// verification without the 0 < r < q and 0 < s < q checks, as done by a sloppy verifier
+pub fn verify_unchecked(
+  params: &DsaParams,
+  y: &BigNumRef,
+  message: &[u8],
+  signature: &DsaSignature,
+) -> Result<bool, ErrorStack> {
+  let hash = hash_message(message)?;
+  let w = mod_inverse(&signature.s, &params.q)?;
+  let u1 = mod_mul(&hash, &w, &params.q)?;
+  let u2 = mod_mul(&signature.r, &w, &params.q)?;
+  let g_u1 = mod_exp(&params.g, &u1, &params.p)?;
+  let y_u2 = mod_exp(y, &u2, &params.p)?;
+  let v = mod_mul(&g_u1, &y_u2, &params.p)?;
+  Ok(nnmod(&v, &params.q)? == signature.r)
+}
+
+pub fn recover_x_from_nonce(
+  params: &DsaParams,
+  hash: &BigNumRef,
+  signature: &DsaSignature,
+  k: &BigNumRef,
+) -> Result<BigNum, ErrorStack> {
+  let sk = mod_mul(&signature.s, k, &params.q)?;
+  let sk_minus_hash = mod_sub(&sk, hash, &params.q)?;
+  let r_inv = mod_inverse(&signature.r, &params.q)?;
+  mod_mul(&sk_minus_hash, &r_inv, &params.q)
+}
+
+// walks g^k for k in 0..=max_k and only derives x once r matches, returns (k, x)
+pub fn brute_force_nonce(
+  params: &DsaParams,
+  y: &BigNumRef,
+  hash: &BigNumRef,
+  signature: &DsaSignature,
+  max_k: u64,
+) -> Result<Option<(BigNum, BigNum)>, ErrorStack> {
+  let mut g_k = BigNum::from_u32(1)?;
+  for k in 0..=max_k {
+    if nnmod(&g_k, &params.q)? == signature.r {
+      let k = bignum::from_u64(k)?;
+      let x = recover_x_from_nonce(params, hash, signature, &k)?;
+      if public_key(params, &x)? == *y {
+        return Ok(Some((k, x)));
+      }
+    }
+    g_k = mod_mul(&g_k, &params.g, &params.p)?;
+  }
+  Ok(None)
+}
+
+pub fn key_fingerprint(x: &BigNumRef) -> Result<String, ErrorStack> {
+  Ok(hex::encode(sha1(x.to_hex_str()?.to_ascii_lowercase().as_bytes())))
+}
+
+// reads the "msg: / s: / r: / m:" blocks of challenge 44
+pub fn read_signed_messages(input_file: &str) -> io::Result<Vec<SignedMessage>> {
+  let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+  let lines: Vec<String> =
+    BufReader::new(File::open(input_file)?).lines().collect::<Result<_, _>>()?;
+  if !lines.len().is_multiple_of(4) {
+    return Err(invalid("Truncated signed message"));
+  }
+  lines
+    .chunks(4)
+    .map(|block| {
+      let field = |idx: usize, name: &str| {
+        let value = block[idx].strip_prefix(name).ok_or_else(|| invalid("Missing field"))?;
+        Ok::<_, io::Error>(value.strip_prefix(' ').unwrap_or(value))
+      };
+      let hash = BigNum::from_hex_str(field(3, "m:")?).map_err(|_| invalid("Invalid hash"))?;
+      let r = BigNum::from_dec_str(field(2, "r:")?).map_err(|_| invalid("Invalid r value"))?;
+      let s = BigNum::from_dec_str(field(1, "s:")?).map_err(|_| invalid("Invalid s value"))?;
+      Ok(SignedMessage {
+        message: field(0, "msg:")?.to_owned(),
+        hash,
+        signature: DsaSignature { r, s },
+      })
+    })
+    .collect()
+}
+
+// any two signatures sharing r were made with the same k:
+// k = (m1 - m2) / (s1 - s2) mod q
+pub fn recover_x_repeated_nonce(
+  params: &DsaParams,
+  y: &BigNumRef,
+  messages: &[SignedMessage],
+) -> Result<Option<BigNum>, ErrorStack> {
+  for (idx, first) in messages.iter().enumerate() {
+    for second in messages.iter().skip(idx + 1) {
+      if first.signature.r != second.signature.r || first.signature.s == second.signature.s {
+        continue;
+      }
+      let hash_diff = mod_sub(&first.hash, &second.hash, &params.q)?;
+      let s_diff = mod_sub(&first.signature.s, &second.signature.s, &params.q)?;
+      let s_diff_inv = mod_inverse(&s_diff, &params.q)?;
+      let k = mod_mul(&hash_diff, &s_diff_inv, &params.q)?;
+      let x = recover_x_from_nonce(params, &first.hash, &first.signature, &k)?;
+      if public_key(params, &x)? == *y {
+        return Ok(Some(x));
+      }
+    }
+  }
+  Ok(None)
+}
+
+// with g = 0 every signature has r = 0 and verifies (without range checks) for any message
+pub fn magic_signature_g_zero() -> Result<DsaSignature, ErrorStack> {
+  Ok(DsaSignature { r: BigNum::from_u32(0)?, s: BigNum::from_u32(1)? })
+}
+
+// with g = p + 1 every power of g is 1, so r = (y^z mod p) mod q and s = r / z verify for any message
+pub fn magic_signature_g_p_plus_one(
+  params: &DsaParams,
+  y: &BigNumRef,
+  z: &BigNumRef,
+) -> Result<DsaSignature, ErrorStack> {
+  let y_z = mod_exp(y, z, &params.p)?;
+  let r = nnmod(&y_z, &params.q)?;
+  let z_inv = mod_inverse(z, &params.q)?;
+  let s = mod_mul(&r, &z_inv, &params.q)?;
+  Ok(DsaSignature { r, s })
+}
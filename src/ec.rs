@@ -0,0 +1,545 @@
+use openssl::{
+  bn::{BigNum, BigNumRef},
+  error::ErrorStack,
+  sha::sha256,
+};
+
+use crate::{
+  bignum::{self, mod_add, mod_inverse, mod_mul, mod_sqrt, mod_sub, nnmod},
+  dlog,
+};
+
+const CRYPTOPALS_P: &str = "233970423115425145524320034830162017933";
+const CRYPTOPALS_A: i32 = -95051;
+const CRYPTOPALS_B: u32 = 11279326;
+const CRYPTOPALS_GX: u32 = 182;
+const CRYPTOPALS_GY: &str = "85518893674295321206118380980485522083";
+const CRYPTOPALS_Q: &str = "29246302889428143187362802287225875743";
+// the same curve as v^2 = u^3 + 534 u^2 + u with u = x - 178
+const CRYPTOPALS_MONTGOMERY_A: u32 = 534;
+const CRYPTOPALS_MONTGOMERY_U: u32 = 4;
+const CRYPTOPALS_COFACTOR: u32 = 8;
+
+#[derive(Debug, PartialEq)]
+pub enum EcPoint {
+  Infinity,
+  Affine { x: BigNum, y: BigNum },
+}
+
+impl Clone for EcPoint {
+  fn clone(&self) -> EcPoint {
+    match self {
+      EcPoint::Infinity => EcPoint::Infinity,
+      EcPoint::Affine { x, y } => {
+        EcPoint::Affine { x: BigNumRef::to_owned(x).unwrap(), y: BigNumRef::to_owned(y).unwrap() }
+      }
+    }
+  }
+}
+
+// Jacobian coordinates: (x, y) = (X / Z^2, Y / Z^3), Z = 0 is the point at infinity
+#[derive(Debug)]
+pub struct ProjectivePoint {
+  pub x: BigNum,
+  pub y: BigNum,
+  pub z: BigNum,
+}
+
+impl ProjectivePoint {
+  pub fn copy(&self) -> Result<ProjectivePoint, ErrorStack> {
+    Ok(ProjectivePoint { x: self.x.to_owned()?, y: self.y.to_owned()?, z: self.z.to_owned()? })
+  }
+}
+
+// y^2 = x^3 + ax + b over GF(p). None of the formulas use b, so points of another curve with the
+// same a go through them unnoticed
+#[derive(Debug)]
+pub struct WeierstrassCurve {
+  pub a: BigNum,
+  pub b: BigNum,
+  pub p: BigNum,
+}
+
+// B v^2 = u^3 + A u^2 + u over GF(p), only B = 1 is supported
+#[derive(Debug)]
+pub struct MontgomeryCurve {
+  pub a: BigNum,
+  pub p: BigNum,
+}
+
+// u : w projective u coordinate, w = 0 is the point at infinity
+#[derive(Debug)]
+pub struct XzPoint {
+  pub u: BigNum,
+  pub w: BigNum,
+}
+
+#[derive(Debug)]
+pub struct EcdhParams {
+  pub curve: WeierstrassCurve,
+  pub base: EcPoint,
+  pub order: BigNum,
+}
+
+impl WeierstrassCurve {
+  pub fn new(a: &BigNumRef, b: &BigNumRef, p: &BigNumRef) -> Result<WeierstrassCurve, ErrorStack> {
+    Ok(WeierstrassCurve { a: nnmod(a, p)?, b: nnmod(b, p)?, p: p.to_owned()? })
+  }
+
+  // same a and p, another b
+  pub fn with_b(&self, b: &BigNumRef) -> Result<WeierstrassCurve, ErrorStack> {
+    WeierstrassCurve::new(&self.a, b, &self.p)
+  }
+
+  fn add_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_add(a, b, &self.p)
+  }
+
+  fn sub_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_sub(a, b, &self.p)
+  }
+
+  fn mul_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_mul(a, b, &self.p)
+  }
+
+  fn inv_mod(&self, a: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_inverse(a, &self.p)
+  }
+
+  fn small_mul(&self, n: u32, a: &BigNum) -> Result<BigNum, ErrorStack> {
+    self.mul_mod(&BigNum::from_u32(n)?, a)
+  }
+
+  // x^3 + ax + b
+  pub fn rhs(&self, x: &BigNum) -> Result<BigNum, ErrorStack> {
+    let x2 = self.mul_mod(x, x)?;
+    let x2_plus_a = self.add_mod(&x2, &self.a)?;
+    let x3_plus_ax = self.mul_mod(&x2_plus_a, x)?;
+    self.add_mod(&x3_plus_ax, &self.b)
+  }
+
+  pub fn contains(&self, point: &EcPoint) -> Result<bool, ErrorStack> {
+    match point {
+      EcPoint::Infinity => Ok(true),
+      EcPoint::Affine { x, y } => Ok(self.mul_mod(y, y)? == self.rhs(x)?),
+    }
+  }
+
+  pub fn neg(&self, point: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    match point {
+      EcPoint::Infinity => Ok(EcPoint::Infinity),
+      EcPoint::Affine { x, y } => {
+        Ok(EcPoint::Affine { x: BigNumRef::to_owned(x)?, y: self.sub_mod(&BigNum::new()?, y)? })
+      }
+    }
+  }
+
+  pub fn add(&self, first: &EcPoint, second: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    let (EcPoint::Affine { x: x1, y: y1 }, EcPoint::Affine { x: x2, y: y2 }) = (first, second)
+    else {
+      return Ok(if *first == EcPoint::Infinity { second.clone() } else { first.clone() });
+    };
+    if x1 == x2 && (*y1 != *y2 || y1.num_bits() == 0) {
+      return Ok(EcPoint::Infinity);
+    }
+    let slope = if x1 == x2 {
+      // (3x^2 + a) / 2y
+      let x1_sq = self.mul_mod(x1, x1)?;
+      let numerator = self.add_mod(&self.small_mul(3, &x1_sq)?, &self.a)?;
+      let denominator = self.inv_mod(&self.small_mul(2, y1)?)?;
+      self.mul_mod(&numerator, &denominator)?
+    } else {
+      let denominator = self.inv_mod(&self.sub_mod(x2, x1)?)?;
+      self.mul_mod(&self.sub_mod(y2, y1)?, &denominator)?
+    };
+    let slope_sq = self.mul_mod(&slope, &slope)?;
+    let x3 = self.sub_mod(&self.sub_mod(&slope_sq, x1)?, x2)?;
+    let y3 = self.sub_mod(&self.mul_mod(&slope, &self.sub_mod(x1, &x3)?)?, y1)?;
+    Ok(EcPoint::Affine { x: x3, y: y3 })
+  }
+
+  pub fn double(&self, point: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    self.add(point, point)
+  }
+
+  pub fn to_projective(&self, point: &EcPoint) -> Result<ProjectivePoint, ErrorStack> {
+    Ok(match point {
+      EcPoint::Infinity => {
+        ProjectivePoint { x: BigNum::from_u32(1)?, y: BigNum::from_u32(1)?, z: BigNum::new()? }
+      }
+      EcPoint::Affine { x, y } => ProjectivePoint {
+        x: BigNumRef::to_owned(x)?,
+        y: BigNumRef::to_owned(y)?,
+        z: BigNum::from_u32(1)?,
+      },
+    })
+  }
+
+  pub fn to_affine(&self, point: &ProjectivePoint) -> Result<EcPoint, ErrorStack> {
+    if point.z.num_bits() == 0 {
+      return Ok(EcPoint::Infinity);
+    }
+    let z_inv = mod_inverse(&point.z, &self.p)?;
+    let z_inv2 = self.mul_mod(&z_inv, &z_inv)?;
+    let z_inv3 = self.mul_mod(&z_inv2, &z_inv)?;
+    Ok(EcPoint::Affine { x: self.mul_mod(&point.x, &z_inv2)?, y: self.mul_mod(&point.y, &z_inv3)? })
+  }
+
+  pub fn projective_double(&self, point: &ProjectivePoint) -> Result<ProjectivePoint, ErrorStack> {
+    if point.z.num_bits() == 0 || point.y.num_bits() == 0 {
+      return self.to_projective(&EcPoint::Infinity);
+    }
+    let (x, y, z) = (&point.x, &point.y, &point.z);
+    let xx = self.mul_mod(x, x)?;
+    let yy = self.mul_mod(y, y)?;
+    let yyyy = self.mul_mod(&yy, &yy)?;
+    let zz = self.mul_mod(z, z)?;
+    // s = 4 x y^2, m = 3 x^2 + a z^4
+    let s = self.small_mul(4, &self.mul_mod(x, &yy)?)?;
+    let m =
+      self.add_mod(&self.small_mul(3, &xx)?, &self.mul_mod(&self.a, &self.mul_mod(&zz, &zz)?)?)?;
+    let x3 = self.sub_mod(&self.mul_mod(&m, &m)?, &self.small_mul(2, &s)?)?;
+    let y3 =
+      self.sub_mod(&self.mul_mod(&m, &self.sub_mod(&s, &x3)?)?, &self.small_mul(8, &yyyy)?)?;
+    let z3 = self.small_mul(2, &self.mul_mod(y, z)?)?;
+    Ok(ProjectivePoint { x: x3, y: y3, z: z3 })
+  }
+
+  pub fn projective_add(
+    &self,
+    first: &ProjectivePoint,
+    second: &ProjectivePoint,
+  ) -> Result<ProjectivePoint, ErrorStack> {
+    if first.z.num_bits() == 0 {
+      return second.copy();
+    }
+    if second.z.num_bits() == 0 {
+      return first.copy();
+    }
+    let z1z1 = self.mul_mod(&first.z, &first.z)?;
+    let z2z2 = self.mul_mod(&second.z, &second.z)?;
+    let u1 = self.mul_mod(&first.x, &z2z2)?;
+    let u2 = self.mul_mod(&second.x, &z1z1)?;
+    let s1 = self.mul_mod(&first.y, &self.mul_mod(&second.z, &z2z2)?)?;
+    let s2 = self.mul_mod(&second.y, &self.mul_mod(&first.z, &z1z1)?)?;
+    if u1 == u2 {
+      return if s1 == s2 {
+        self.projective_double(first)
+      } else {
+        self.to_projective(&EcPoint::Infinity)
+      };
+    }
+    let h = self.sub_mod(&u2, &u1)?;
+    let r = self.sub_mod(&s2, &s1)?;
+    let hh = self.mul_mod(&h, &h)?;
+    let hhh = self.mul_mod(&h, &hh)?;
+    let v = self.mul_mod(&u1, &hh)?;
+    let x3 = self.sub_mod(&self.sub_mod(&self.mul_mod(&r, &r)?, &hhh)?, &self.small_mul(2, &v)?)?;
+    let y3 =
+      self.sub_mod(&self.mul_mod(&r, &self.sub_mod(&v, &x3)?)?, &self.mul_mod(&s1, &hhh)?)?;
+    let z3 = self.mul_mod(&self.mul_mod(&first.z, &second.z)?, &h)?;
+    Ok(ProjectivePoint { x: x3, y: y3, z: z3 })
+  }
+
+  // double-and-add in projective coordinates, k must not be negative
+  pub fn scalar_mul(&self, k: &BigNumRef, point: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    let base = self.to_projective(point)?;
+    let mut res = self.to_projective(&EcPoint::Infinity)?;
+    for bit in (0..k.num_bits()).rev() {
+      res = self.projective_double(&res)?;
+      if k.is_bit_set(bit) {
+        res = self.projective_add(&res, &base)?;
+      }
+    }
+    self.to_affine(&res)
+  }
+
+  // Montgomery ladder: the same double and add for every bit, r1 - r0 stays the input point
+  pub fn ladder(&self, k: &BigNumRef, point: &EcPoint) -> Result<EcPoint, ErrorStack> {
+    let mut r0 = self.to_projective(&EcPoint::Infinity)?;
+    let mut r1 = self.to_projective(point)?;
+    for bit in (0..k.num_bits()).rev() {
+      if k.is_bit_set(bit) {
+        r0 = self.projective_add(&r0, &r1)?;
+        r1 = self.projective_double(&r1)?;
+      } else {
+        r1 = self.projective_add(&r0, &r1)?;
+        r0 = self.projective_double(&r0)?;
+      }
+    }
+    self.to_affine(&r0)
+  }
+
+  pub fn random_point(&self) -> Result<EcPoint, ErrorStack> {
+    loop {
+      let x = bignum::random_below(&self.p)?;
+      let rhs = self.rhs(&x)?;
+      if let Some(y) = mod_sqrt(&rhs, &self.p)? {
+        return Ok(EcPoint::Affine { x, y });
+      }
+    }
+  }
+
+  // a point of prime order r, given the order of the whole group. The r-part of the group need
+  // not be cyclic, so the cofactor strips every power of r and the point is then multiplied by r
+  // until the next step would reach infinity
+  pub fn point_of_order(&self, group_order: &BigNumRef, r: u32) -> Result<EcPoint, ErrorStack> {
+    let mut cofactor = group_order.to_owned()?;
+    while cofactor.mod_word(r)? == 0 {
+      cofactor = &cofactor / &BigNum::from_u32(r)?;
+    }
+    let r = BigNum::from_u32(r)?;
+    loop {
+      let mut point = self.scalar_mul(&cofactor, &self.random_point()?)?;
+      if point == EcPoint::Infinity {
+        continue;
+      }
+      loop {
+        let next = self.scalar_mul(&r, &point)?;
+        if next == EcPoint::Infinity {
+          return Ok(point);
+        }
+        point = next;
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct MontgomeryParams {
+  pub curve: MontgomeryCurve,
+  pub base: BigNum,
+  pub order: BigNum,
+  // number of points on the whole curve, a multiple of `order`
+  pub group_order: BigNum,
+}
+
+impl MontgomeryCurve {
+  pub fn new(a: &BigNumRef, p: &BigNumRef) -> Result<MontgomeryCurve, ErrorStack> {
+    Ok(MontgomeryCurve { a: nnmod(a, p)?, p: p.to_owned()? })
+  }
+
+  fn add_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_add(a, b, &self.p)
+  }
+
+  fn sub_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_sub(a, b, &self.p)
+  }
+
+  fn mul_mod(&self, a: &BigNum, b: &BigNum) -> Result<BigNum, ErrorStack> {
+    mod_mul(a, b, &self.p)
+  }
+
+  // u^3 + A u^2 + u
+  pub fn rhs(&self, u: &BigNum) -> Result<BigNum, ErrorStack> {
+    let u_plus_a = self.add_mod(u, &self.a)?;
+    let u2_plus_au = self.mul_mod(&u_plus_a, u)?;
+    let one = BigNum::from_u32(1)?;
+    self.mul_mod(&self.add_mod(&u2_plus_au, &one)?, u)
+  }
+
+  // every u is either on the curve or on its quadratic twist, where the right hand side is not a
+  // square. The ladder below works the same on both
+  pub fn on_curve(&self, u: &BigNum) -> Result<bool, ErrorStack> {
+    let rhs = self.rhs(u)?;
+    Ok(mod_sqrt(&rhs, &self.p)?.is_some())
+  }
+
+  // #E + #E' = 2p + 2
+  pub fn twist_order(&self, group_order: &BigNumRef) -> Result<BigNum, ErrorStack> {
+    let two = BigNum::from_u32(2)?;
+    Ok(&(&(&self.p * &two) + &two) - group_order)
+  }
+
+  pub fn random_twist_point(&self) -> Result<BigNum, ErrorStack> {
+    loop {
+      let u = bignum::random_below(&self.p)?;
+      if !self.on_curve(&u)? {
+        return Ok(u);
+      }
+    }
+  }
+
+  pub fn to_xz(&self, u: &BigNumRef) -> Result<XzPoint, ErrorStack> {
+    Ok(XzPoint { u: u.to_owned()?, w: BigNum::from_u32(1)? })
+  }
+
+  // the point at infinity comes out as 0, like the order 2 point (0, 0)
+  pub fn to_u(&self, point: &XzPoint) -> Result<BigNum, ErrorStack> {
+    if point.w.num_bits() == 0 {
+      return BigNum::new();
+    }
+    self.mul_mod(&point.u, &mod_inverse(&point.w, &self.p)?)
+  }
+
+  // 2P: u = (u^2 - w^2)^2, w = 4uw (u^2 + Auw + w^2)
+  pub fn x_double(&self, point: &XzPoint) -> Result<XzPoint, ErrorStack> {
+    let uu = self.mul_mod(&point.u, &point.u)?;
+    let ww = self.mul_mod(&point.w, &point.w)?;
+    let uw = self.mul_mod(&point.u, &point.w)?;
+    let diff = self.sub_mod(&uu, &ww)?;
+    let sum = self.add_mod(&self.add_mod(&uu, &ww)?, &self.mul_mod(&self.a, &uw)?)?;
+    let four_uw = self.mul_mod(&BigNum::from_u32(4)?, &uw)?;
+    Ok(XzPoint { u: self.mul_mod(&diff, &diff)?, w: self.mul_mod(&four_uw, &sum)? })
+  }
+
+  // P + Q from P, Q and P - Q
+  pub fn x_add(
+    &self,
+    first: &XzPoint,
+    second: &XzPoint,
+    difference: &XzPoint,
+  ) -> Result<XzPoint, ErrorStack> {
+    let u =
+      self.sub_mod(&self.mul_mod(&first.u, &second.u)?, &self.mul_mod(&first.w, &second.w)?)?;
+    let w =
+      self.sub_mod(&self.mul_mod(&first.u, &second.w)?, &self.mul_mod(&first.w, &second.u)?)?;
+    Ok(XzPoint {
+      u: self.mul_mod(&difference.w, &self.mul_mod(&u, &u)?)?,
+      w: self.mul_mod(&difference.u, &self.mul_mod(&w, &w)?)?,
+    })
+  }
+
+  // u(kP) from u(P) alone, r1 - r0 stays P
+  pub fn ladder(&self, u: &BigNumRef, k: &BigNumRef) -> Result<BigNum, ErrorStack> {
+    let point = self.to_xz(u)?;
+    let mut r0 = XzPoint { u: BigNum::from_u32(1)?, w: BigNum::new()? };
+    let mut r1 = self.to_xz(u)?;
+    for bit in (0..k.num_bits()).rev() {
+      if k.is_bit_set(bit) {
+        r0 = self.x_add(&r0, &r1, &point)?;
+        r1 = self.x_double(&r1)?;
+      } else {
+        r1 = self.x_add(&r0, &r1, &point)?;
+        r0 = self.x_double(&r0)?;
+      }
+    }
+    self.to_u(&r0)
+  }
+
+  // a twist point whose order is exactly the product of the distinct primes `factors`, all of
+  // which divide the twist order exactly once
+  pub fn twist_point_of_order(
+    &self,
+    twist_order: &BigNumRef,
+    factors: &[u32],
+  ) -> Result<BigNum, ErrorStack> {
+    let mut order = BigNum::from_u32(1)?;
+    for r in factors {
+      order = &order * &BigNum::from_u32(*r)?;
+    }
+    let cofactor = twist_order / &order;
+    loop {
+      let random = self.random_twist_point()?;
+      let u = self.ladder(&random, &cofactor)?;
+      let mut exact = true;
+      for r in factors {
+        let quotient = &order / &BigNum::from_u32(*r)?;
+        exact &= self.ladder(&u, &quotient)?.num_bits() != 0;
+      }
+      if exact {
+        return Ok(u);
+      }
+    }
+  }
+
+  // y^2 = x^3 + ax + b with x = u + A / 3
+  pub fn to_weierstrass(&self) -> Result<WeierstrassCurve, ErrorStack> {
+    let a2 = self.mul_mod(&self.a, &self.a)?;
+    let a3 = self.mul_mod(&a2, &self.a)?;
+    let three = BigNum::from_u32(3)?;
+    let a = self.mul_mod(&self.sub_mod(&three, &a2)?, &mod_inverse(&three, &self.p)?)?;
+    let two_a3 = self.mul_mod(&BigNum::from_u32(2)?, &a3)?;
+    let nine_a = self.mul_mod(&BigNum::from_u32(9)?, &self.a)?;
+    let twenty_seven = BigNum::from_u32(27)?;
+    let inverse_27 = mod_inverse(&twenty_seven, &self.p)?;
+    let b = self.mul_mod(&self.sub_mod(&two_a3, &nine_a)?, &inverse_27)?;
+    WeierstrassCurve::new(&a, &b, &self.p)
+  }
+
+  // one of the two Weierstrass points with this u, None on the twist
+  pub fn to_weierstrass_point(&self, u: &BigNumRef) -> Result<Option<EcPoint>, ErrorStack> {
+    let three = BigNum::from_u32(3)?;
+    let third = self.mul_mod(&self.a, &mod_inverse(&three, &self.p)?)?;
+    let u = u.to_owned()?;
+    let rhs = self.rhs(&u)?;
+    let Some(y) = mod_sqrt(&rhs, &self.p)? else {
+      return Ok(None);
+    };
+    Ok(Some(EcPoint::Affine { x: self.add_mod(&u, &third)?, y }))
+  }
+}
+
+// Pollard's kangaroo: the k in [lower, upper] with k * generator = target
+pub fn kangaroo(
+  curve: &WeierstrassCurve,
+  generator: &EcPoint,
+  target: &EcPoint,
+  lower: &BigNumRef,
+  upper: &BigNumRef,
+) -> Result<Option<BigNum>, ErrorStack> {
+  dlog::kangaroo(curve, generator, target, lower, upper)
+}
+
+pub fn cryptopals_params() -> Result<EcdhParams, ErrorStack> {
+  let p = BigNum::from_dec_str(CRYPTOPALS_P)?;
+  let a = BigNum::from_dec_str(&CRYPTOPALS_A.to_string())?;
+  let b = BigNum::from_u32(CRYPTOPALS_B)?;
+  let curve = WeierstrassCurve::new(&a, &b, &p)?;
+  let base = EcPoint::Affine {
+    x: BigNum::from_u32(CRYPTOPALS_GX)?,
+    y: BigNum::from_dec_str(CRYPTOPALS_GY)?,
+  };
+  Ok(EcdhParams { curve, base, order: BigNum::from_dec_str(CRYPTOPALS_Q)? })
+}
+
+pub fn cryptopals_montgomery_params() -> Result<MontgomeryParams, ErrorStack> {
+  let p = BigNum::from_dec_str(CRYPTOPALS_P)?;
+  let a = BigNum::from_u32(CRYPTOPALS_MONTGOMERY_A)?;
+  let curve = MontgomeryCurve::new(&a, &p)?;
+  let order = BigNum::from_dec_str(CRYPTOPALS_Q)?;
+  let group_order = &order * &BigNum::from_u32(CRYPTOPALS_COFACTOR)?;
+  Ok(MontgomeryParams {
+    curve,
+    base: BigNum::from_u32(CRYPTOPALS_MONTGOMERY_U)?,
+    order,
+    group_order,
+  })
+}
+
+// (secret, public)
+pub fn generate_keypair(params: &EcdhParams) -> Result<(BigNum, EcPoint), ErrorStack> {
+  let mut secret = bignum::random_below(&params.order)?;
+  while secret.num_bits() == 0 {
+    secret = bignum::random_below(&params.order)?;
+  }
+  let public = params.curve.ladder(&secret, &params.base)?;
+  Ok((secret, public))
+}
+
+pub fn shared_secret(
+  curve: &WeierstrassCurve,
+  secret: &BigNumRef,
+  public: &EcPoint,
+) -> Result<EcPoint, ErrorStack> {
+  curve.ladder(secret, public)
+}
+
+// sha256 of x || y, the point at infinity hashes the empty string
+pub fn shared_key(point: &EcPoint) -> Vec<u8> {
+  match point {
+    EcPoint::Infinity => sha256(b"").to_vec(),
+    EcPoint::Affine { x, y } => {
+      let mut bytes = x.to_vec();
+      bytes.extend(y.to_vec());
+      sha256(&bytes).to_vec()
+    }
+  }
+}
+
+// sha256 of the u coordinate alone
+pub fn shared_key_x_only(u: &BigNumRef) -> Vec<u8> {
+  sha256(&u.to_vec()).to_vec()
+}
@@ -0,0 +1,222 @@
+use itertools::Itertools;
+
+use crate::{
+  cryptog::aes_128_ecb_encrypt,
+  gf2::{BitMatrix, from_column, monomial, mul_matrix, square_matrix},
+  gf128::{Gf128, Poly},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidTag {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcmMessage {
+  pub aad: Vec<u8>,
+  pub ciphertext: Vec<u8>,
+  pub tag: Vec<u8>,
+}
+
+fn aes_block(key: &[u8], block: &[u8]) -> Vec<u8> {
+  aes_128_ecb_encrypt(key, block).unwrap()
+}
+
+// authentication key H = E(K, 0^128)
+pub fn auth_key(key: &[u8]) -> Gf128 {
+  Gf128::from_bytes(&aes_block(key, &[0; 16]))
+}
+
+// the aad and ciphertext blocks, each zero padded, then the bit lengths of both
+fn ghash_blocks(aad: &[u8], ciphertext: &[u8]) -> Vec<Gf128> {
+  let mut lengths = (aad.len() as u64 * 8).to_be_bytes().to_vec();
+  lengths.extend((ciphertext.len() as u64 * 8).to_be_bytes());
+  aad
+    .chunks(16)
+    .chain(ciphertext.chunks(16))
+    .chain([lengths.as_slice()])
+    .map(Gf128::from_bytes)
+    .collect()
+}
+
+pub fn ghash(h: Gf128, aad: &[u8], ciphertext: &[u8]) -> Gf128 {
+  ghash_blocks(aad, ciphertext).into_iter().fold(Gf128::ZERO, |acc, block| (acc + block) * h)
+}
+
+// GHASH as a polynomial in H: the i-th of n blocks is the coefficient of H^(n - i)
+pub fn ghash_poly(aad: &[u8], ciphertext: &[u8]) -> Poly {
+  let mut coefficients = ghash_blocks(aad, ciphertext);
+  coefficients.push(Gf128::ZERO);
+  coefficients.reverse();
+  Poly::new(coefficients)
+}
+
+// 96-bit nonce || 32-bit big endian counter, the counter starts at 1 for the tag mask
+fn counter_block(nonce: &[u8], counter: u32) -> Vec<u8> {
+  let mut block = nonce.to_owned();
+  block.extend(counter.to_be_bytes());
+  block
+}
+
+fn gcm_ctr(key: &[u8], nonce: &[u8], input: &[u8]) -> Vec<u8> {
+  input
+    .chunks(16)
+    .enumerate()
+    .flat_map(|(i, chunk)| {
+      let keystream = aes_block(key, &counter_block(nonce, i as u32 + 2));
+      chunk.iter().zip(keystream).map(|(a, b)| a ^ b).collect::<Vec<u8>>()
+    })
+    .collect()
+}
+
+fn tag(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+  let mask = Gf128::from_bytes(&aes_block(key, &counter_block(nonce, 1)));
+  (ghash(auth_key(key), aad, ciphertext) + mask).to_bytes().to_vec()
+}
+
+// returns (ciphertext, tag)
+pub fn aes_gcm_encrypt(
+  key: &[u8],
+  nonce: &[u8],
+  plaintext: &[u8],
+  aad: &[u8],
+) -> (Vec<u8>, Vec<u8>) {
+  aes_gcm_encrypt_truncated(key, nonce, plaintext, aad, 16)
+}
+
+pub fn aes_gcm_decrypt(
+  key: &[u8],
+  nonce: &[u8],
+  ciphertext: &[u8],
+  aad: &[u8],
+  tag: &[u8],
+) -> Result<Vec<u8>, InvalidTag> {
+  aes_gcm_decrypt_truncated(key, nonce, ciphertext, aad, tag, 16)
+}
+
+// only the first `tag_len` bytes of the tag are kept
+pub fn aes_gcm_encrypt_truncated(
+  key: &[u8],
+  nonce: &[u8],
+  plaintext: &[u8],
+  aad: &[u8],
+  tag_len: usize,
+) -> (Vec<u8>, Vec<u8>) {
+  assert!(key.len() == 16, "Only AES-128 keys are supported");
+  assert!(nonce.len() == 12, "Only 96-bit nonces are supported");
+  assert!((1..=16).contains(&tag_len), "Tags are 1 to 16 bytes long");
+  let ciphertext = gcm_ctr(key, nonce, plaintext);
+  let mut tag = tag(key, nonce, aad, &ciphertext);
+  tag.truncate(tag_len);
+  (ciphertext, tag)
+}
+
+pub fn aes_gcm_decrypt_truncated(
+  key: &[u8],
+  nonce: &[u8],
+  ciphertext: &[u8],
+  aad: &[u8],
+  tag: &[u8],
+  tag_len: usize,
+) -> Result<Vec<u8>, InvalidTag> {
+  assert!(key.len() == 16, "Only AES-128 keys are supported");
+  assert!(nonce.len() == 12, "Only 96-bit nonces are supported");
+  assert!((1..=16).contains(&tag_len), "Tags are 1 to 16 bytes long");
+  let expected = self::tag(key, nonce, aad, ciphertext);
+  // no early exit on the first differing byte
+  if tag.len() != tag_len || expected.iter().zip(tag).fold(0, |acc, (a, b)| acc | (a ^ b)) != 0 {
+    return Err(InvalidTag {});
+  }
+  Ok(gcm_ctr(key, nonce, ciphertext))
+}
+
+// with a repeated nonce both tags share the mask s, so H is a root of
+// ghash_poly(first) + ghash_poly(second) + first.tag + second.tag
+pub fn forbidden_nonce_candidates(first: &GcmMessage, second: &GcmMessage) -> Vec<Gf128> {
+  let difference = ghash_poly(&first.aad, &first.ciphertext)
+    .add(&ghash_poly(&second.aad, &second.ciphertext))
+    .add(&Poly::constant(Gf128::from_bytes(&first.tag) + Gf128::from_bytes(&second.tag)));
+  difference.roots()
+}
+
+// valid tag for any aad and ciphertext under the nonce of `known`, given its H
+pub fn forge_tag(h: Gf128, known: &GcmMessage, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+  let mask = Gf128::from_bytes(&known.tag) + ghash(h, &known.aad, &known.ciphertext);
+  (ghash(h, aad, ciphertext) + mask).to_bytes().to_vec()
+}
+
+const FORGERIES_PER_ROUND: usize = 4;
+
+// Ferguson: flipping the blocks multiplied by H^(2^i) changes the tag by sum(d_i H^(2^i)), which
+// is linear in the bits of H: Ad h. Picking the d_i so the first rows of Ad vanish makes a forgery
+// of a truncated tag likely, and every accepted forgery gives linear equations on h. The
+// ciphertext (no aad) must be 2^n full blocks and the oracle answers for (ciphertext, tag)
+pub fn ferguson_attack(
+  ciphertext: &[u8],
+  tag: &[u8],
+  oracle_fn: impl Fn(&[u8], &[u8]) -> bool,
+) -> Gf128 {
+  let blocks = ciphertext.len() / 16;
+  let whole_blocks = ciphertext.len().is_multiple_of(16) && blocks.is_power_of_two();
+  assert!(whole_blocks && blocks >= 4, "The ciphertext must be 2^n blocks with n >= 2");
+  let n = blocks.ilog2() as usize;
+  let tag_bits = tag.len() * 8;
+  // (block index, Ms^i) for the block multiplied by H^(2^i), i = 1..=n
+  let square = square_matrix();
+  let mut squares = vec![];
+  let mut power = BitMatrix::identity(128);
+  for i in 1..=n {
+    power = square.mul(&power);
+    squares.push((blocks + 2 - (1 << i), power.clone()));
+  }
+  let monomials: Vec<BitMatrix> = (0..128).map(|b| mul_matrix(monomial(b))).collect();
+  // d is one row holding the n differences d_i, 128 bits each
+  let d_block = |d: &BitMatrix, i: usize| {
+    (0..128).filter(|b| d.get(0, i * 128 + b)).fold(Gf128::ZERO, |acc, b| acc + monomial(b))
+  };
+  let ad = |d: &BitMatrix| -> BitMatrix {
+    squares.iter().enumerate().fold(BitMatrix::zero(128, 128), |acc, (i, (_, power))| {
+      acc.add(&mul_matrix(d_block(d, i)).mul(power))
+    })
+  };
+  // equations known to hold for h, and a basis of their solutions as columns of x
+  let mut equations = BitMatrix::zero(0, 128);
+  let mut x = BitMatrix::identity(128);
+  while x.cols() > 1 {
+    let dim = x.cols();
+    // zeroing k rows of Ad x costs k * dim equations on the n * 128 bits of the d_i
+    let k = (tag_bits - 1).min(n * 128 / dim - 1);
+    let projected: Vec<BitMatrix> = squares.iter().map(|(_, power)| power.mul(&x)).collect();
+    let mut dependency = BitMatrix::zero(k * dim, n * 128);
+    for (i, projected) in projected.iter().enumerate() {
+      for (b, monomial) in monomials.iter().enumerate() {
+        let rows = monomial.top_rows(k).mul(projected);
+        for (r, c) in (0..k).cartesian_product(0..dim).filter(|(r, c)| rows.get(*r, *c)) {
+          dependency.set(r * dim + c, i * 128 + b, true);
+        }
+      }
+    }
+    let kernel = dependency.kernel();
+    // every accepted forgery still holds for the true h, a few of them share one kernel
+    let mut forgeries = 0;
+    while forgeries < FORGERIES_PER_ROUND {
+      let d = (0..kernel.rows())
+        .filter(|_| rand::random::<bool>())
+        .fold(BitMatrix::zero(1, n * 128), |acc, row| acc.add(&kernel.row(row)));
+      if d.is_zero() {
+        continue;
+      }
+      let mut forged = ciphertext.to_owned();
+      for (i, (block, _)) in squares.iter().enumerate() {
+        let start = (block - 1) * 16;
+        let d_i = d_block(&d, i).to_bytes();
+        forged[start..start + 16].iter_mut().zip(d_i).for_each(|(a, b)| *a ^= b);
+      }
+      if oracle_fn(&forged, tag) {
+        // the whole truncated tag came out unchanged: Ad h vanishes on its first tag_bits rows
+        equations = equations.stack(&ad(&d).top_rows(tag_bits));
+        forgeries += 1;
+      }
+    }
+    x = equations.kernel().transpose();
+  }
+  from_column(&x, 0)
+}
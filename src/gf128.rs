@@ -0,0 +1,370 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+// element of GF(2^128) in GCM's bit order: the x^0 coefficient is the most significant bit of
+// the first byte, so a block read as a big endian u128 has x^i at bit 127 - i
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Gf128(pub u128);
+
+// x^128 + x^7 + x^2 + x + 1 with the bits reflected
+const R: u128 = 0xe1 << 120;
+
+impl Gf128 {
+  pub const ZERO: Gf128 = Gf128(0);
+  pub const ONE: Gf128 = Gf128(1 << 127);
+
+  // shorter blocks are padded with zeros
+  pub fn from_bytes(block: &[u8]) -> Gf128 {
+    assert!(block.len() <= 16, "A field element is at most 16 bytes");
+    let mut bytes = [0u8; 16];
+    bytes[..block.len()].copy_from_slice(block);
+    Gf128(u128::from_be_bytes(bytes))
+  }
+
+  pub fn to_bytes(self) -> [u8; 16] {
+    self.0.to_be_bytes()
+  }
+
+  pub fn random() -> Gf128 {
+    Gf128(rand::random())
+  }
+
+  pub fn is_zero(self) -> bool {
+    self.0 == 0
+  }
+
+  pub fn pow(self, exponent: u128) -> Gf128 {
+    let mut res = Gf128::ONE;
+    let mut base = self;
+    let mut exponent = exponent;
+    while exponent > 0 {
+      if exponent & 1 == 1 {
+        res = res * base;
+      }
+      base = base * base;
+      exponent >>= 1;
+    }
+    res
+  }
+
+  // a^(2^128 - 2), panics on zero
+  pub fn inverse(self) -> Gf128 {
+    assert!(!self.is_zero(), "Zero has no inverse");
+    self.pow(u128::MAX - 1)
+  }
+
+  // squaring is a bijection in characteristic 2, a^(2^127) undoes it
+  pub fn sqrt(self) -> Gf128 {
+    self.pow(1 << 127)
+  }
+}
+
+impl Add for Gf128 {
+  type Output = Gf128;
+
+  // coefficients live in GF(2)
+  #[allow(clippy::suspicious_arithmetic_impl)]
+  fn add(self, rhs: Gf128) -> Gf128 {
+    Gf128(self.0 ^ rhs.0)
+  }
+}
+
+impl Sub for Gf128 {
+  type Output = Gf128;
+
+  #[allow(clippy::suspicious_arithmetic_impl)]
+  fn sub(self, rhs: Gf128) -> Gf128 {
+    self + rhs
+  }
+}
+
+impl Mul for Gf128 {
+  type Output = Gf128;
+
+  fn mul(self, rhs: Gf128) -> Gf128 {
+    let mut res = 0u128;
+    let mut v = rhs.0;
+    for i in 0..128 {
+      if (self.0 >> (127 - i)) & 1 == 1 {
+        res ^= v;
+      }
+      v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+    }
+    Gf128(res)
+  }
+}
+
+impl Div for Gf128 {
+  type Output = Gf128;
+
+  #[allow(clippy::suspicious_arithmetic_impl)]
+  fn div(self, rhs: Gf128) -> Gf128 {
+    self * rhs.inverse()
+  }
+}
+
+// polynomial over GF(2^128), coefficient i belongs to x^i and there are no trailing zeros
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Poly(Vec<Gf128>);
+
+impl Poly {
+  pub fn new(coefficients: Vec<Gf128>) -> Poly {
+    let mut res = Poly(coefficients);
+    res.normalize();
+    res
+  }
+
+  pub fn zero() -> Poly {
+    Poly(vec![])
+  }
+
+  pub fn one() -> Poly {
+    Poly(vec![Gf128::ONE])
+  }
+
+  pub fn constant(c: Gf128) -> Poly {
+    Poly::new(vec![c])
+  }
+
+  pub fn x() -> Poly {
+    Poly(vec![Gf128::ZERO, Gf128::ONE])
+  }
+
+  // x - root
+  pub fn linear(root: Gf128) -> Poly {
+    Poly(vec![root, Gf128::ONE])
+  }
+
+  pub fn random(degree: usize) -> Poly {
+    Poly::new((0..=degree).map(|_| Gf128::random()).collect())
+  }
+
+  pub fn coefficients(&self) -> &[Gf128] {
+    &self.0
+  }
+
+  fn normalize(&mut self) {
+    while self.0.last().is_some_and(|v| v.is_zero()) {
+      self.0.pop();
+    }
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn is_one(&self) -> bool {
+    self.0 == [Gf128::ONE]
+  }
+
+  // the zero polynomial has no degree
+  pub fn degree(&self) -> Option<usize> {
+    self.0.len().checked_sub(1)
+  }
+
+  pub fn leading(&self) -> Gf128 {
+    self.0.last().copied().unwrap_or_default()
+  }
+
+  pub fn eval(&self, x: Gf128) -> Gf128 {
+    self.0.iter().rev().fold(Gf128::ZERO, |acc, c| acc * x + *c)
+  }
+
+  pub fn scale(&self, c: Gf128) -> Poly {
+    Poly::new(self.0.iter().map(|v| *v * c).collect())
+  }
+
+  pub fn monic(&self) -> Poly {
+    if self.is_zero() { Poly::zero() } else { self.scale(self.leading().inverse()) }
+  }
+
+  pub fn add(&self, rhs: &Poly) -> Poly {
+    let len = self.0.len().max(rhs.0.len());
+    let coefficient = |p: &Poly, i: usize| p.0.get(i).copied().unwrap_or_default();
+    Poly::new((0..len).map(|i| coefficient(self, i) + coefficient(rhs, i)).collect())
+  }
+
+  pub fn mul(&self, rhs: &Poly) -> Poly {
+    if self.is_zero() || rhs.is_zero() {
+      return Poly::zero();
+    }
+    let mut res = vec![Gf128::ZERO; self.0.len() + rhs.0.len() - 1];
+    for (i, a) in self.0.iter().enumerate() {
+      for (j, b) in rhs.0.iter().enumerate() {
+        res[i + j] = res[i + j] + *a * *b;
+      }
+    }
+    Poly::new(res)
+  }
+
+  // (quotient, remainder), panics when dividing by zero
+  pub fn div_rem(&self, rhs: &Poly) -> (Poly, Poly) {
+    let rhs_degree = rhs.degree().expect("Division by the zero polynomial");
+    let lead_inv = rhs.leading().inverse();
+    let mut rem = self.0.clone();
+    let mut quotient = vec![Gf128::ZERO; self.0.len().saturating_sub(rhs_degree)];
+    while rem.len() > rhs_degree {
+      let shift = rem.len() - 1 - rhs_degree;
+      let factor = *rem.last().unwrap() * lead_inv;
+      quotient[shift] = factor;
+      for (i, c) in rhs.0.iter().enumerate() {
+        rem[shift + i] = rem[shift + i] + factor * *c;
+      }
+      rem.pop();
+      while rem.last().is_some_and(|v| v.is_zero()) {
+        rem.pop();
+      }
+    }
+    (Poly::new(quotient), Poly::new(rem))
+  }
+
+  pub fn rem(&self, rhs: &Poly) -> Poly {
+    self.div_rem(rhs).1
+  }
+
+  pub fn div_exact(&self, rhs: &Poly) -> Poly {
+    let (quotient, rem) = self.div_rem(rhs);
+    assert!(rem.is_zero(), "Polynomial division left a remainder");
+    quotient
+  }
+
+  // monic greatest common divisor
+  pub fn gcd(&self, rhs: &Poly) -> Poly {
+    let (mut a, mut b) = (self.clone(), rhs.clone());
+    while !b.is_zero() {
+      let rem = a.rem(&b);
+      a = b;
+      b = rem;
+    }
+    a.monic()
+  }
+
+  pub fn derivative(&self) -> Poly {
+    // in characteristic 2 only the odd powers survive
+    Poly::new(
+      self
+        .0
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| if i % 2 == 1 { *c } else { Gf128::ZERO })
+        .collect(),
+    )
+  }
+
+  // g with g^2 = self, only valid when every odd coefficient is zero
+  fn sqrt(&self) -> Poly {
+    Poly::new(self.0.iter().step_by(2).map(|c| c.sqrt()).collect())
+  }
+
+  // self^(2^k) mod modulus by repeated squaring
+  pub fn pow_2k_mod(&self, k: usize, modulus: &Poly) -> Poly {
+    (0..k).fold(self.rem(modulus), |acc, _| acc.mul(&acc).rem(modulus))
+  }
+
+  // monic square-free factors with their multiplicity
+  pub fn square_free_factorization(&self) -> Vec<(Poly, usize)> {
+    let f = self.monic();
+    let mut res = vec![];
+    let mut c = f.gcd(&f.derivative());
+    let mut w = f.div_exact(&c);
+    let mut i = 1;
+    while !w.is_one() {
+      let y = w.gcd(&c);
+      let factor = w.div_exact(&y);
+      if !factor.is_one() {
+        res.push((factor, i));
+      }
+      w = y;
+      c = c.div_exact(&w);
+      i += 1;
+    }
+    // what is left is a perfect square
+    if !c.is_one() {
+      res.extend(c.sqrt().square_free_factorization().into_iter().map(|(v, m)| (v, m * 2)));
+    }
+    res
+  }
+
+  // splits a monic square-free polynomial into products of irreducibles of the same degree
+  pub fn distinct_degree_factorization(&self) -> Vec<(Poly, usize)> {
+    let mut res = vec![];
+    let mut f = self.monic();
+    let mut h = Poly::x();
+    let mut degree = 1;
+    while f.degree().unwrap_or(0) >= 2 * degree {
+      // x^(q^degree) with q = 2^128
+      h = h.pow_2k_mod(128, &f);
+      let g = f.gcd(&h.add(&Poly::x()));
+      if !g.is_one() {
+        f = f.div_exact(&g);
+        h = h.rem(&f);
+        res.push((g, degree));
+      }
+      degree += 1;
+    }
+    if f.degree().unwrap_or(0) > 0 {
+      let d = f.degree().unwrap();
+      res.push((f, d));
+    }
+    res
+  }
+
+  // Cantor-Zassenhaus for a monic square-free product of irreducibles of degree `degree`. In
+  // characteristic 2 the trace a + a^2 + ... + a^(2^(128 degree - 1)) takes the place of
+  // a^((q^d - 1) / 2) and is 0 or 1 modulo each factor with equal odds
+  pub fn equal_degree_factorization(&self, degree: usize) -> Vec<Poly> {
+    let f = self.monic();
+    let n = f.degree().unwrap_or(0);
+    let mut factors = vec![f.clone()];
+    while factors.len() < n / degree {
+      let mut term = Poly::random(n - 1).rem(&f);
+      let mut trace = term.clone();
+      for _ in 1..128 * degree {
+        term = term.mul(&term).rem(&f);
+        trace = trace.add(&term);
+      }
+      factors = factors
+        .into_iter()
+        .flat_map(|u| {
+          let g = u.gcd(&trace);
+          if u.degree() == Some(degree) || g.is_one() || g == u {
+            vec![u]
+          } else {
+            let other = u.div_exact(&g);
+            vec![g, other]
+          }
+        })
+        .collect();
+    }
+    factors
+  }
+
+  // full factorisation into monic irreducibles with multiplicity
+  pub fn factor(&self) -> Vec<(Poly, usize)> {
+    self
+      .square_free_factorization()
+      .into_iter()
+      .flat_map(|(square_free, multiplicity)| {
+        square_free.distinct_degree_factorization().into_iter().flat_map(move |(v, degree)| {
+          v.equal_degree_factorization(degree).into_iter().map(move |f| (f, multiplicity))
+        })
+      })
+      .collect()
+  }
+
+  // distinct roots, i.e. the constant terms of the monic linear factors
+  pub fn roots(&self) -> Vec<Gf128> {
+    if self.degree().unwrap_or(0) == 0 {
+      return vec![];
+    }
+    let square_free: Vec<Poly> =
+      self.square_free_factorization().into_iter().map(|(v, _)| v).collect();
+    square_free
+      .iter()
+      .flat_map(|v| v.distinct_degree_factorization())
+      .filter(|(_, degree)| *degree == 1)
+      .flat_map(|(v, _)| v.equal_degree_factorization(1))
+      .map(|v| v.coefficients()[0])
+      .collect()
+  }
+}
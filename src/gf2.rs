@@ -0,0 +1,193 @@
+use crate::gf128::Gf128;
+
+// dense matrix over GF(2), every row packed into 64-bit words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+  rows: usize,
+  cols: usize,
+  data: Vec<Vec<u64>>,
+}
+
+impl BitMatrix {
+  pub fn zero(rows: usize, cols: usize) -> BitMatrix {
+    BitMatrix { rows, cols, data: vec![vec![0; cols.div_ceil(64)]; rows] }
+  }
+
+  pub fn identity(n: usize) -> BitMatrix {
+    let mut res = BitMatrix::zero(n, n);
+    (0..n).for_each(|i| res.set(i, i, true));
+    res
+  }
+
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  pub fn get(&self, row: usize, col: usize) -> bool {
+    (self.data[row][col / 64] >> (col % 64)) & 1 == 1
+  }
+
+  pub fn set(&mut self, row: usize, col: usize, value: bool) {
+    let mask = 1 << (col % 64);
+    if value {
+      self.data[row][col / 64] |= mask;
+    } else {
+      self.data[row][col / 64] &= !mask;
+    }
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.data.iter().flatten().all(|v| *v == 0)
+  }
+
+  fn xor_row_into(&mut self, src: usize, dst: usize) {
+    let (src_row, dst_row) = if src < dst {
+      let (head, tail) = self.data.split_at_mut(dst);
+      (&head[src], &mut tail[0])
+    } else {
+      let (head, tail) = self.data.split_at_mut(src);
+      (&tail[0], &mut head[dst])
+    };
+    dst_row.iter_mut().zip(src_row).for_each(|(a, b)| *a ^= b);
+  }
+
+  pub fn add(&self, rhs: &BitMatrix) -> BitMatrix {
+    assert!(self.rows == rhs.rows && self.cols == rhs.cols, "Matrix dimensions don't match");
+    let mut res = self.clone();
+    res.data.iter_mut().flatten().zip(rhs.data.iter().flatten()).for_each(|(a, b)| *a ^= b);
+    res
+  }
+
+  pub fn row(&self, row: usize) -> BitMatrix {
+    BitMatrix { rows: 1, cols: self.cols, data: vec![self.data[row].clone()] }
+  }
+
+  // the first `n` rows only
+  pub fn top_rows(&self, n: usize) -> BitMatrix {
+    BitMatrix { rows: n, cols: self.cols, data: self.data[..n].to_owned() }
+  }
+
+  // rows of `self` followed by the rows of `other`
+  pub fn stack(&self, other: &BitMatrix) -> BitMatrix {
+    assert!(self.cols == other.cols, "Stacked matrices need the same number of columns");
+    let mut data = self.data.clone();
+    data.extend_from_slice(&other.data);
+    BitMatrix { rows: self.rows + other.rows, cols: self.cols, data }
+  }
+
+  pub fn transpose(&self) -> BitMatrix {
+    let mut res = BitMatrix::zero(self.cols, self.rows);
+    for row in 0..self.rows {
+      for col in 0..self.cols {
+        if self.get(row, col) {
+          res.set(col, row, true);
+        }
+      }
+    }
+    res
+  }
+
+  pub fn mul(&self, rhs: &BitMatrix) -> BitMatrix {
+    assert!(self.cols == rhs.rows, "Matrix dimensions don't line up");
+    let mut res = BitMatrix::zero(self.rows, rhs.cols);
+    for (row, res_row) in res.data.iter_mut().enumerate() {
+      for k in (0..self.cols).filter(|k| self.get(row, *k)) {
+        res_row.iter_mut().zip(&rhs.data[k]).for_each(|(a, b)| *a ^= b);
+      }
+    }
+    res
+  }
+
+  // reduced row echelon form in place, returns the pivot columns
+  pub fn row_reduce(&mut self) -> Vec<usize> {
+    let mut pivots = vec![];
+    for col in 0..self.cols {
+      let row = pivots.len();
+      if row == self.rows {
+        break;
+      }
+      let Some(pivot) = (row..self.rows).find(|r| self.get(*r, col)) else {
+        continue;
+      };
+      self.data.swap(row, pivot);
+      for other in 0..self.rows {
+        if other != row && self.get(other, col) {
+          self.xor_row_into(row, other);
+        }
+      }
+      pivots.push(col);
+    }
+    pivots
+  }
+
+  pub fn rank(&self) -> usize {
+    self.clone().row_reduce().len()
+  }
+
+  // basis of { v : self v = 0 }, one vector per row of the result
+  pub fn kernel(&self) -> BitMatrix {
+    let mut reduced = self.clone();
+    let pivots = reduced.row_reduce();
+    let free: Vec<usize> = (0..self.cols).filter(|v| !pivots.contains(v)).collect();
+    let mut res = BitMatrix::zero(free.len(), self.cols);
+    for (i, col) in free.iter().enumerate() {
+      res.set(i, *col, true);
+      for (row, pivot) in pivots.iter().enumerate() {
+        if reduced.get(row, *col) {
+          res.set(i, *pivot, true);
+        }
+      }
+    }
+    res
+  }
+
+  // applies a 128x128 matrix to a field element seen as a vector of coefficients
+  pub fn mul_gf128(&self, v: Gf128) -> Gf128 {
+    assert!(self.rows == 128 && self.cols == 128, "Expected a 128x128 matrix");
+    from_column(&self.mul(&to_column(v)), 0)
+  }
+}
+
+// entry i is the coefficient of x^i
+pub fn coefficient(v: Gf128, i: usize) -> bool {
+  (v.0 >> (127 - i)) & 1 == 1
+}
+
+// x^i
+pub fn monomial(i: usize) -> Gf128 {
+  Gf128(1 << (127 - i))
+}
+
+pub fn to_column(v: Gf128) -> BitMatrix {
+  let mut res = BitMatrix::zero(128, 1);
+  (0..128).for_each(|i| res.set(i, 0, coefficient(v, i)));
+  res
+}
+
+pub fn from_column(m: &BitMatrix, col: usize) -> Gf128 {
+  (0..128).filter(|i| m.get(*i, col)).fold(Gf128::ZERO, |acc, i| acc + monomial(i))
+}
+
+// matrix of v -> c v
+pub fn mul_matrix(c: Gf128) -> BitMatrix {
+  let mut res = BitMatrix::zero(128, 128);
+  for col in 0..128 {
+    let product = c * monomial(col);
+    (0..128).for_each(|row| res.set(row, col, coefficient(product, row)));
+  }
+  res
+}
+
+// matrix of v -> v^2, squaring is linear in characteristic 2
+pub fn square_matrix() -> BitMatrix {
+  let mut res = BitMatrix::zero(128, 128);
+  for col in 0..128 {
+    let square = monomial(col) * monomial(col);
+    (0..128).for_each(|row| res.set(row, col, coefficient(square, row)));
+  }
+  res
+}
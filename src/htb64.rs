@@ -1,10 +1,19 @@
-use std::{char, fmt::Display};
-use byteorder::{BigEndian, ByteOrder};
+use std::{fmt::Display, fs, io};
+
+use hex::Case;
+
+pub mod base32;
+pub mod base58;
+pub mod base64;
+pub mod base85;
+pub mod hex;
+pub mod pem;
 
 #[derive(Debug, Clone)]
 enum BadHexErrorKind {
   BadChar,
   BadSize,
+  SmallBuffer,
 }
 
 #[derive(Debug, Clone)]
@@ -25,36 +34,116 @@ impl Display for BadHexError {
       BadHexErrorKind::BadSize => {
         write!(f, "Invalid size for hex string, hex string must be divisible by 2")
       }
+      BadHexErrorKind::SmallBuffer => {
+        let position = self.position;
+        write!(f, "Output buffer too small for hex string, {position} bytes are needed")
+      }
     }
   }
 }
 
-fn get_hex_val(c1: u8, c2: u8, idx: usize) -> Result<u8, BadHexError> {
-  let v1 = match c1 {
-    b'A'..=b'F' => (c1 + 9) << 4,
-    b'a'..=b'f' => (c1 + 9) << 4,
-    b'0'..=b'9' => (c1 & 0b00001111) << 4,
-    _ => {
-      return Err(BadHexError {
-        error_kind: BadHexErrorKind::BadChar,
-        character: char::from(c1),
-        position: idx * 2,
-      });
+#[derive(Debug, Clone)]
+enum BadEncodingErrorKind {
+  BadChar,
+  BadSize,
+  BadPadding,
+  TrailingBits,
+  Overflow,
+  BadArmor,
+}
+
+// a decoding error of any of the text encodings
+#[derive(Debug, Clone)]
+pub struct BadEncodingError {
+  encoding: &'static str,
+  error_kind: BadEncodingErrorKind,
+  character: char,
+  position: usize,
+}
+
+#[allow(dead_code)]
+impl BadEncodingError {
+  // offset of the faulty symbol in the text
+  pub fn position(&self) -> usize {
+    self.position
+  }
+}
+
+impl Display for BadEncodingError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let encoding = self.encoding;
+    let position = self.position;
+    match self.error_kind {
+      BadEncodingErrorKind::BadChar => {
+        let character = self.character;
+        write!(f, "Invalid char for {encoding} string '{character}' at position {position}")
+      }
+      BadEncodingErrorKind::BadSize => {
+        write!(f, "Invalid size for {encoding} string, incomplete group at position {position}")
+      }
+      BadEncodingErrorKind::BadPadding => {
+        write!(f, "Invalid padding for {encoding} string at position {position}")
+      }
+      BadEncodingErrorKind::TrailingBits => {
+        write!(f, "Invalid trailing bits for {encoding} string at position {position}")
+      }
+      BadEncodingErrorKind::Overflow => {
+        write!(f, "Group too large for {encoding} string at position {position}")
+      }
+      BadEncodingErrorKind::BadArmor => {
+        write!(f, "Invalid armor for {encoding} text at position {position}")
+      }
     }
-  };
-  let res = match c2 {
-    b'A'..=b'F' => v1 | (c2 + 9) & 0x0f,
-    b'a'..=b'f' => v1 | (c2 + 9) & 0x0f,
-    b'0'..=b'9' => v1 | (c2 & 0b00001111),
-    _ => {
-      return Err(BadHexError {
-        error_kind: BadHexErrorKind::BadChar,
-        character: char::from(c2),
-        position: idx * 2 + 1,
-      });
+  }
+}
+
+impl From<BadHexError> for BadEncodingError {
+  fn from(err: BadHexError) -> BadEncodingError {
+    let error_kind = match err.error_kind {
+      BadHexErrorKind::BadChar => BadEncodingErrorKind::BadChar,
+      BadHexErrorKind::BadSize | BadHexErrorKind::SmallBuffer => BadEncodingErrorKind::BadSize,
+    };
+    BadEncodingError {
+      encoding: "hex",
+      error_kind,
+      character: err.character,
+      position: err.position,
     }
+  }
+}
+
+// a text form of binary data. parse takes the loose form found in files and pasted from other
+// tools, with whitespace and line breaks between the symbols
+pub trait Encoding {
+  fn name(&self) -> &'static str;
+  fn to_text(&self, bytes: &[u8]) -> String;
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError>;
+}
+
+pub const ENCODING_NAMES: [&str; 9] =
+  ["hex", "base64", "base64url", "base32", "base32hex", "base58", "ascii85", "z85", "pem"];
+
+// one of ENCODING_NAMES, case insensitive
+pub fn encoding_by_name(name: &str) -> Option<Box<dyn Encoding>> {
+  let res: Box<dyn Encoding> = match name.to_ascii_lowercase().as_str() {
+    "hex" => Box::new(hex::Hex(Case::Lower)),
+    "base64" => Box::new(base64::STANDARD),
+    "base64url" => Box::new(base64::URL_SAFE),
+    "base32" => Box::new(base32::STANDARD),
+    "base32hex" => Box::new(base32::HEX),
+    "base58" => Box::new(base58::Base58),
+    "ascii85" => Box::new(base85::Variant::Ascii85),
+    "z85" => Box::new(base85::Variant::Z85),
+    "pem" => Box::new(pem::Armor::default()),
+    _ => return None,
   };
-  Ok(res)
+  Some(res)
+}
+
+// the whole file decoded, bad text is an InvalidData error
+pub fn read_encoded(path: &str, encoding: &dyn Encoding) -> io::Result<Vec<u8>> {
+  let text = fs::read(path)?;
+  encoding.parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
 }
 
 pub fn hex_bytes_to_bytes(hex_bytes: &[u8]) -> Result<Vec<u8>, BadHexError> {
@@ -62,62 +151,18 @@ pub fn hex_bytes_to_bytes(hex_bytes: &[u8]) -> Result<Vec<u8>, BadHexError> {
     // we will assume we can only convert from complete 8 byte chunks
     return Err(BadHexError { error_kind: BadHexErrorKind::BadSize, position: 0, character: '\0' });
   }
-  hex_bytes
-      .chunks(2)
-      .enumerate()
-      .map(|(i, cs)| get_hex_val(cs[0], cs[1], i))
-      .collect::<Result<Vec<u8>, BadHexError>>()
+  hex::decode(hex_bytes)
 }
 
-pub fn bytes_to_hex(bytes :&[u8]) -> String{
-  bytes.iter().map(|b| {
-    let left = b >> 4;
-    let right = b & 0x0f;
-    let res:String = match (left,right) {
-      (0..=9, 0..=9) => String::from_utf8(vec![left|0b0011_0000,right|0b0011_0000]).unwrap(),
-      (a,0..=9) => String::from_utf8(vec![(a-9)|0b0100_0000,right|0b0011_0000]).unwrap(),
-      (0..=9,b) => String::from_utf8(vec![left|0b0011_0000,(b-9)|0b0100_0000]).unwrap(),
-      (a,b) => String::from_utf8(vec![(a-9)|0b0100_0000,(b-9)|0b0100_0000]).unwrap(),
-    };
-    res
-  }).collect::<String>()
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+  hex::encode_with(bytes, Case::Upper)
 }
 
-#[allow(dead_code)]
-fn hex_bytes_to_base64(hex_bytes: &[u8]) -> Result<String, BadHexError> {
-  let bin_codon = hex_bytes_to_bytes(hex_bytes)?;
-  let mut res: Vec<u8> = vec![0, 0, 0, 0];
-  if hex_bytes.len() == 6 {
-    let bin_uint = BigEndian::read_u24(bin_codon.as_ref());
-    res[0] = BASE64_SYMBOLS[((bin_uint & 0x00fc0000) >> 18) as usize];
-    res[1] = BASE64_SYMBOLS[((bin_uint & 0x0003f000) >> 12) as usize];
-    res[2] = BASE64_SYMBOLS[((bin_uint & 0x00000fc0) >> 6) as usize];
-    res[3] = BASE64_SYMBOLS[(bin_uint & 0x0000003f) as usize];
-  } else if hex_bytes.len() == 4 {
-    let bin_uint = BigEndian::read_u16(bin_codon.as_ref());
-    res[0] = BASE64_SYMBOLS[((bin_uint & 0xfc00) >> 10) as usize];
-    res[1] = BASE64_SYMBOLS[((bin_uint & 0x03f0) >> 4) as usize];
-    res[2] = BASE64_SYMBOLS[((bin_uint & 0x000f) << 2) as usize];
-    res[3] = b'=';
-  } else {
-    let bin_uint = bin_codon[0];
-    res[0] = BASE64_SYMBOLS[((bin_uint & 0xfc) >> 2) as usize];
-    res[1] = BASE64_SYMBOLS[((bin_uint & 0x03) << 4) as usize];
-    res[2] = b'=';
-    res[3] = b'=';
-  }
-
-  Ok(String::from_utf8(res).expect("Invalid UTF-8 for base 64 string"))
-}
-
-const BASE64_SYMBOLS: &[u8] =
-  "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/".as_bytes();
-
 #[allow(dead_code)]
 pub fn hex_to_base64(hex_string_bin: &[u8]) -> Result<String, BadHexError> {
   if hex_string_bin.len() % 2 == 1 {
     // we will assume we can only convert from complete 8 bit chunks (a byte)
     return Err(BadHexError { error_kind: BadHexErrorKind::BadSize, position: 0, character: '\0' });
   }
-  hex_string_bin.chunks(6).map(hex_bytes_to_base64).collect()
+  Ok(base64::STANDARD.encode(&hex::decode(hex_string_bin)?))
 }
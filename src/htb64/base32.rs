@@ -0,0 +1,148 @@
+use super::{BadEncodingError, BadEncodingErrorKind, Encoding};
+
+const STANDARD_SYMBOLS: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const HEX_SYMBOLS: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+// symbols in a full group of 5 bytes
+const GROUP_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+  // RFC 4648 section 6
+  #[default]
+  Standard,
+  // RFC 4648 section 7, sorts like the bytes it encodes
+  Hex,
+}
+
+impl Alphabet {
+  fn symbols(&self) -> &'static [u8; 32] {
+    match self {
+      Alphabet::Standard => STANDARD_SYMBOLS,
+      Alphabet::Hex => HEX_SYMBOLS,
+    }
+  }
+
+  fn value(&self, c: u8) -> Option<u8> {
+    self.symbols().iter().position(|v| *v == c).map(|v| v as u8)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+  pub alphabet: Alphabet,
+  pub pad: bool,
+}
+
+pub const STANDARD: Config = Config { alphabet: Alphabet::Standard, pad: true };
+pub const STANDARD_NO_PAD: Config = Config { alphabet: Alphabet::Standard, pad: false };
+pub const HEX: Config = Config { alphabet: Alphabet::Hex, pad: true };
+pub const HEX_NO_PAD: Config = Config { alphabet: Alphabet::Hex, pad: false };
+
+impl Config {
+  pub fn encode(&self, bytes: &[u8]) -> String {
+    let symbols = self.alphabet.symbols();
+    let mut res = Vec::with_capacity(bytes.len().div_ceil(5) * GROUP_LEN);
+    for chunk in bytes.chunks(5) {
+      let mut buf = [0; 8];
+      buf[3..3 + chunk.len()].copy_from_slice(chunk);
+      let n = u64::from_be_bytes(buf);
+      // every byte starts a symbol, plus the one holding the last bits
+      let len = (chunk.len() * 8).div_ceil(5);
+      res.extend((0..len).map(|idx| symbols[(n >> (35 - 5 * idx) & 0x1f) as usize]));
+      if self.pad {
+        res.resize(res.len() + GROUP_LEN - len, b'=');
+      }
+    }
+    String::from_utf8(res).unwrap()
+  }
+
+  // uppercase symbols of the alphabet, padding exactly as configured and zero unused bits
+  pub fn decode(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    self.decode_with(text, false)
+  }
+
+  // whitespace anywhere, either case, optional padding and any unused bits
+  pub fn decode_lenient(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    self.decode_with(text, true)
+  }
+
+  fn decode_with(&self, text: &[u8], lenient: bool) -> Result<Vec<u8>, BadEncodingError> {
+    let error = |error_kind, position, c: u8| BadEncodingError {
+      encoding: self.name(),
+      error_kind,
+      character: char::from(c),
+      position,
+    };
+    let mut res = Vec::with_capacity(text.len() * 5 / GROUP_LEN);
+    let mut buf = 0u64;
+    let mut bits = 0;
+    let mut symbols = 0;
+    let mut padding = 0;
+    let mut last = 0;
+    for (position, c) in text.iter().enumerate() {
+      if lenient && c.is_ascii_whitespace() {
+        continue;
+      }
+      if *c == b'=' {
+        if !(lenient || self.pad) || symbols % GROUP_LEN == 0 {
+          return Err(error(BadEncodingErrorKind::BadPadding, position, *c));
+        }
+        padding += 1;
+        if symbols % GROUP_LEN + padding > GROUP_LEN {
+          return Err(error(BadEncodingErrorKind::BadPadding, position, *c));
+        }
+        continue;
+      }
+      if padding > 0 {
+        return Err(error(BadEncodingErrorKind::BadPadding, position, *c));
+      }
+      let c = if lenient { c.to_ascii_uppercase() } else { *c };
+      let value = self
+        .alphabet
+        .value(c)
+        .ok_or_else(|| error(BadEncodingErrorKind::BadChar, position, text[position]))?;
+      buf = buf << 5 | u64::from(value);
+      bits += 5;
+      symbols += 1;
+      last = position;
+      if bits >= 8 {
+        bits -= 8;
+        res.push((buf >> bits) as u8);
+        buf &= (1 << bits) - 1;
+      }
+    }
+    // a last group of 1, 3 or 6 symbols doesn't end on a byte
+    if matches!(symbols % GROUP_LEN, 1 | 3 | 6) {
+      return Err(error(BadEncodingErrorKind::BadSize, last, text[last]));
+    }
+    if !lenient
+      && self.pad
+      && symbols % GROUP_LEN != 0
+      && symbols % GROUP_LEN + padding != GROUP_LEN
+    {
+      return Err(error(BadEncodingErrorKind::BadPadding, text.len(), 0));
+    }
+    if !lenient && buf != 0 {
+      return Err(error(BadEncodingErrorKind::TrailingBits, last, text[last]));
+    }
+    Ok(res)
+  }
+}
+
+// parsed with decode_lenient
+impl Encoding for Config {
+  fn name(&self) -> &'static str {
+    match self.alphabet {
+      Alphabet::Standard => "base32",
+      Alphabet::Hex => "base32hex",
+    }
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    self.encode(bytes)
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    self.decode_lenient(text)
+  }
+}
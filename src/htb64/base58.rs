@@ -0,0 +1,91 @@
+use super::{BadEncodingError, BadEncodingErrorKind, Encoding};
+
+// Bitcoin alphabet, without 0, O, I and l
+const SYMBOLS: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// the bytes as a big-endian number in base 58, every leading zero byte kept as a '1'
+pub fn encode(bytes: &[u8]) -> String {
+  let zeros = bytes.iter().take_while(|v| **v == 0).count();
+  // little-endian base 58 digits
+  let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+  for b in &bytes[zeros..] {
+    let mut carry = u32::from(*b);
+    for digit in digits.iter_mut() {
+      carry += u32::from(*digit) << 8;
+      *digit = (carry % 58) as u8;
+      carry /= 58;
+    }
+    while carry > 0 {
+      digits.push((carry % 58) as u8);
+      carry /= 58;
+    }
+  }
+  let mut res = vec![SYMBOLS[0]; zeros];
+  res.extend(digits.iter().rev().map(|v| SYMBOLS[*v as usize]));
+  String::from_utf8(res).unwrap()
+}
+
+pub fn decode(text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+  decode_with(text, false)
+}
+
+// whitespace anywhere
+pub fn decode_lenient(text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+  decode_with(text, true)
+}
+
+fn decode_with(text: &[u8], lenient: bool) -> Result<Vec<u8>, BadEncodingError> {
+  let mut zeros = 0;
+  let mut leading = true;
+  // little-endian bytes
+  let mut bytes: Vec<u8> = vec![];
+  for (position, c) in text.iter().enumerate() {
+    if lenient && c.is_ascii_whitespace() {
+      continue;
+    }
+    let Some(value) = SYMBOLS.iter().position(|v| v == c) else {
+      return Err(BadEncodingError {
+        encoding: "base58",
+        error_kind: BadEncodingErrorKind::BadChar,
+        character: char::from(*c),
+        position,
+      });
+    };
+    if leading && value == 0 {
+      zeros += 1;
+      continue;
+    }
+    leading = false;
+    let mut carry = value as u32;
+    for b in bytes.iter_mut() {
+      carry += u32::from(*b) * 58;
+      *b = carry as u8;
+      carry >>= 8;
+    }
+    while carry > 0 {
+      bytes.push(carry as u8);
+      carry >>= 8;
+    }
+  }
+  let mut res = vec![0; zeros];
+  res.extend(bytes.iter().rev());
+  Ok(res)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Base58;
+
+// parsed with decode_lenient
+impl Encoding for Base58 {
+  fn name(&self) -> &'static str {
+    "base58"
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    encode(bytes)
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    decode_lenient(text)
+  }
+}
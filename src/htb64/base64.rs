@@ -0,0 +1,417 @@
+use std::{
+  fmt::Display,
+  io::{self, Read, Write},
+};
+
+use super::{BadEncodingError, BadEncodingErrorKind, Encoding};
+
+const STANDARD_SYMBOLS: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_SYMBOLS: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const MIME_LINE_LEN: usize = 76;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alphabet {
+  // RFC 4648 section 4, + and /
+  #[default]
+  Standard,
+  // RFC 4648 section 5, - and _
+  UrlSafe,
+}
+
+impl Alphabet {
+  fn symbols(&self) -> &'static [u8; 64] {
+    match self {
+      Alphabet::Standard => STANDARD_SYMBOLS,
+      Alphabet::UrlSafe => URL_SAFE_SYMBOLS,
+    }
+  }
+
+  fn value(&self, c: u8) -> Option<u8> {
+    match c {
+      b'A'..=b'Z' => Some(c - b'A'),
+      b'a'..=b'z' => Some(c - b'a' + 26),
+      b'0'..=b'9' => Some(c - b'0' + 52),
+      b'+' if *self == Alphabet::Standard => Some(62),
+      b'/' if *self == Alphabet::Standard => Some(63),
+      b'-' if *self == Alphabet::UrlSafe => Some(62),
+      b'_' if *self == Alphabet::UrlSafe => Some(63),
+      _ => None,
+    }
+  }
+}
+
+// how text is encoded and what a strict decode accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+  pub alphabet: Alphabet,
+  pub pad: bool,
+  // line length of the output, lines end with \r\n. Some(0) doesn't wrap either
+  pub wrap: Option<usize>,
+}
+
+pub const STANDARD: Config = Config { alphabet: Alphabet::Standard, pad: true, wrap: None };
+pub const STANDARD_NO_PAD: Config = Config { alphabet: Alphabet::Standard, pad: false, wrap: None };
+pub const URL_SAFE: Config = Config { alphabet: Alphabet::UrlSafe, pad: true, wrap: None };
+pub const URL_SAFE_NO_PAD: Config = Config { alphabet: Alphabet::UrlSafe, pad: false, wrap: None };
+// RFC 2045, padded and wrapped at 76 characters
+pub const MIME: Config =
+  Config { alphabet: Alphabet::Standard, pad: true, wrap: Some(MIME_LINE_LEN) };
+
+impl Config {
+  fn line_len(&self) -> Option<usize> {
+    self.wrap.filter(|v| *v > 0)
+  }
+
+  pub fn encode<T: AsRef<[u8]>>(&self, bytes: T) -> String {
+    let bytes = bytes.as_ref();
+    let mut encoder = Encoder::new(*self);
+    let mut res = Vec::with_capacity(bytes.len().div_ceil(3) * 4);
+    encoder.push(bytes, &mut res);
+    encoder.finish(&mut res);
+    String::from_utf8(res).unwrap()
+  }
+
+  // only the symbols of the alphabet, padding exactly as configured, a \r\n after every full line
+  // when wrapped and zero unused bits in the last symbol
+  pub fn decode<T: AsRef<[u8]>>(&self, text: T) -> Result<Vec<u8>, BadBase64Error> {
+    Decoder::new(*self, false).decode(text.as_ref())
+  }
+
+  // whitespace anywhere, both alphabets, optional padding and any unused bits
+  pub fn decode_lenient<T: AsRef<[u8]>>(&self, text: T) -> Result<Vec<u8>, BadBase64Error> {
+    Decoder::new(*self, true).decode(text.as_ref())
+  }
+}
+
+#[derive(Debug, Clone)]
+enum BadBase64ErrorKind {
+  BadChar,
+  BadPadding,
+  Truncated,
+  TrailingBits,
+}
+
+#[derive(Debug, Clone)]
+pub struct BadBase64Error {
+  // name of the config that failed to decode, base64 or base64url
+  encoding: &'static str,
+  error_kind: BadBase64ErrorKind,
+  character: char,
+  position: usize,
+}
+
+impl BadBase64Error {
+  // offset of the faulty symbol in the text
+  pub fn position(&self) -> usize {
+    self.position
+  }
+}
+
+impl Display for BadBase64Error {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let (encoding, position) = (self.encoding, self.position);
+    match self.error_kind {
+      BadBase64ErrorKind::BadChar => {
+        let character = self.character;
+        write!(f, "Invalid char for {encoding} string '{character}' at position {position}")
+      }
+      BadBase64ErrorKind::BadPadding => {
+        write!(f, "Invalid padding for {encoding} string at position {position}")
+      }
+      BadBase64ErrorKind::Truncated => {
+        write!(f, "Truncated {encoding} string, lone symbol at position {position}")
+      }
+      BadBase64ErrorKind::TrailingBits => {
+        write!(f, "Invalid trailing bits for {encoding} string at position {position}")
+      }
+    }
+  }
+}
+
+impl From<BadBase64Error> for BadEncodingError {
+  fn from(err: BadBase64Error) -> BadEncodingError {
+    let error_kind = match err.error_kind {
+      BadBase64ErrorKind::BadChar => BadEncodingErrorKind::BadChar,
+      BadBase64ErrorKind::BadPadding => BadEncodingErrorKind::BadPadding,
+      BadBase64ErrorKind::Truncated => BadEncodingErrorKind::BadSize,
+      BadBase64ErrorKind::TrailingBits => BadEncodingErrorKind::TrailingBits,
+    };
+    BadEncodingError {
+      encoding: err.encoding,
+      error_kind,
+      character: err.character,
+      position: err.position,
+    }
+  }
+}
+
+// parsed with decode_lenient
+impl Encoding for Config {
+  fn name(&self) -> &'static str {
+    match self.alphabet {
+      Alphabet::Standard => "base64",
+      Alphabet::UrlSafe => "base64url",
+    }
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    self.encode(bytes)
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    Ok(self.decode_lenient(text)?)
+  }
+}
+
+// symbol by symbol encoder, keeps the bytes of an incomplete group between pushes
+#[derive(Debug, Clone)]
+struct Encoder {
+  config: Config,
+  pending: Vec<u8>,
+  column: usize,
+}
+
+impl Encoder {
+  fn new(config: Config) -> Encoder {
+    Encoder { config, pending: vec![], column: 0 }
+  }
+
+  fn push(&mut self, bytes: &[u8], out: &mut Vec<u8>) {
+    let taken = (3 - self.pending.len()).min(bytes.len());
+    self.pending.extend_from_slice(&bytes[..taken]);
+    if self.pending.len() < 3 {
+      return;
+    }
+    let group = std::mem::take(&mut self.pending);
+    self.group(&group, out);
+    let mut chunks = bytes[taken..].chunks_exact(3);
+    for chunk in chunks.by_ref() {
+      self.group(chunk, out);
+    }
+    self.pending.extend_from_slice(chunks.remainder());
+  }
+
+  fn finish(&mut self, out: &mut Vec<u8>) {
+    if !self.pending.is_empty() {
+      let group = std::mem::take(&mut self.pending);
+      self.group(&group, out);
+    }
+  }
+
+  // 1 to 3 bytes
+  fn group(&mut self, bytes: &[u8], out: &mut Vec<u8>) {
+    let symbols = self.config.alphabet.symbols();
+    let mut buf = [0; 3];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    let n = u32::from(buf[0]) << 16 | u32::from(buf[1]) << 8 | u32::from(buf[2]);
+    for idx in 0..4 {
+      if idx <= bytes.len() {
+        self.symbol(symbols[(n >> (18 - 6 * idx) & 0x3f) as usize], out);
+      } else if self.config.pad {
+        self.symbol(b'=', out);
+      }
+    }
+  }
+
+  fn symbol(&mut self, c: u8, out: &mut Vec<u8>) {
+    if self.config.line_len().is_some_and(|v| self.column == v) {
+      out.extend_from_slice(b"\r\n");
+      self.column = 0;
+    }
+    out.push(c);
+    self.column += 1;
+  }
+}
+
+// gathers symbols into groups of 4 and tracks the column of wrapped text
+#[derive(Debug, Clone)]
+struct Decoder {
+  config: Config,
+  lenient: bool,
+  // the symbols of the group being read
+  buf: u32,
+  symbols: usize,
+  padding: usize,
+  position: usize,
+  // position of the last symbol read
+  last: usize,
+  // symbols read on the current line, one more once its \r is read
+  column: usize,
+}
+
+impl Decoder {
+  fn new(config: Config, lenient: bool) -> Decoder {
+    Decoder { config, lenient, buf: 0, symbols: 0, padding: 0, position: 0, last: 0, column: 0 }
+  }
+
+  fn decode(mut self, text: &[u8]) -> Result<Vec<u8>, BadBase64Error> {
+    let mut res = Vec::with_capacity(text.len() / 4 * 3);
+    text.iter().try_for_each(|c| self.push(*c, &mut res))?;
+    self.finish(&mut res)?;
+    Ok(res)
+  }
+
+  fn error(&self, error_kind: BadBase64ErrorKind, position: usize, c: u8) -> BadBase64Error {
+    BadBase64Error { encoding: self.config.name(), error_kind, character: char::from(c), position }
+  }
+
+  fn push(&mut self, c: u8, out: &mut Vec<u8>) -> Result<(), BadBase64Error> {
+    let position = self.position;
+    self.position += 1;
+    if self.lenient && c.is_ascii_whitespace() {
+      return Ok(());
+    }
+    if let Some(line_len) = self.config.line_len().filter(|_| !self.lenient) {
+      match c {
+        b'\r' if self.column == line_len => {
+          self.column += 1;
+          return Ok(());
+        }
+        b'\n' if self.column == line_len + 1 => {
+          self.column = 0;
+          return Ok(());
+        }
+        _ if self.column >= line_len => {
+          return Err(self.error(BadBase64ErrorKind::BadChar, position, c));
+        }
+        _ => self.column += 1,
+      }
+    }
+    if c == b'=' {
+      // padding completes a group of 2 or 3 symbols
+      let allowed = self.lenient || self.config.pad;
+      if !allowed || self.symbols < 2 || self.symbols + self.padding == 4 {
+        return Err(self.error(BadBase64ErrorKind::BadPadding, position, c));
+      }
+      self.padding += 1;
+      return Ok(());
+    }
+    if self.padding > 0 {
+      return Err(self.error(BadBase64ErrorKind::BadPadding, position, c));
+    }
+    let value = match self.config.alphabet.value(c) {
+      Some(v) => v,
+      None if self.lenient => match c {
+        b'+' | b'-' => 62,
+        b'/' | b'_' => 63,
+        _ => return Err(self.error(BadBase64ErrorKind::BadChar, position, c)),
+      },
+      None => return Err(self.error(BadBase64ErrorKind::BadChar, position, c)),
+    };
+    self.buf = self.buf << 6 | u32::from(value);
+    self.symbols += 1;
+    self.last = position;
+    if self.symbols == 4 {
+      out.extend_from_slice(&self.buf.to_be_bytes()[1..]);
+      self.buf = 0;
+      self.symbols = 0;
+    }
+    Ok(())
+  }
+
+  fn finish(&mut self, out: &mut Vec<u8>) -> Result<(), BadBase64Error> {
+    // a \r without its \n
+    if self.config.line_len().is_some_and(|v| self.column > v) {
+      return Err(self.error(BadBase64ErrorKind::BadChar, self.position - 1, b'\r'));
+    }
+    let (symbols, padding) = (self.symbols, self.padding);
+    self.symbols = 0;
+    self.padding = 0;
+    match symbols {
+      0 => return Ok(()),
+      1 => return Err(self.error(BadBase64ErrorKind::Truncated, self.last, 0)),
+      _ => {}
+    }
+    if !self.lenient && self.config.pad && symbols + padding != 4 {
+      return Err(self.error(BadBase64ErrorKind::BadPadding, self.position, 0));
+    }
+    // 2 symbols hold 1 byte and 4 unused bits, 3 symbols hold 2 bytes and 2 unused bits
+    let unused = 6 * symbols % 8;
+    if !self.lenient && self.buf & ((1 << unused) - 1) != 0 {
+      return Err(self.error(BadBase64ErrorKind::TrailingBits, self.last, 0));
+    }
+    let bytes = (self.buf >> unused).to_be_bytes();
+    out.extend_from_slice(&bytes[4 - (symbols - 1)..]);
+    self.buf = 0;
+    Ok(())
+  }
+}
+
+// encodes everything written to it into the inner writer. The last incomplete group is only
+// written by finish
+#[derive(Debug)]
+pub struct Base64Writer<W: Write> {
+  inner: W,
+  encoder: Encoder,
+}
+
+impl<W: Write> Base64Writer<W> {
+  pub fn new(inner: W, config: Config) -> Base64Writer<W> {
+    Base64Writer { inner, encoder: Encoder::new(config) }
+  }
+
+  pub fn finish(mut self) -> io::Result<W> {
+    let mut out = vec![];
+    self.encoder.finish(&mut out);
+    self.inner.write_all(&out)?;
+    self.inner.flush()?;
+    Ok(self.inner)
+  }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let mut out = vec![];
+    self.encoder.push(buf, &mut out);
+    self.inner.write_all(&out)?;
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+// decodes the text read from the inner reader, bad input is an InvalidData error
+#[derive(Debug)]
+pub struct Base64Reader<R: Read> {
+  inner: R,
+  decoder: Decoder,
+  // decoded bytes not returned yet
+  pending: Vec<u8>,
+  done: bool,
+}
+
+impl<R: Read> Base64Reader<R> {
+  pub fn new(inner: R, config: Config) -> Base64Reader<R> {
+    Base64Reader { inner, decoder: Decoder::new(config, false), pending: vec![], done: false }
+  }
+
+  // accepts what decode_lenient accepts
+  pub fn lenient(inner: R, config: Config) -> Base64Reader<R> {
+    Base64Reader { inner, decoder: Decoder::new(config, true), pending: vec![], done: false }
+  }
+}
+
+impl<R: Read> Read for Base64Reader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let invalid = |err: BadBase64Error| io::Error::new(io::ErrorKind::InvalidData, err.to_string());
+    while self.pending.is_empty() && !self.done && !buf.is_empty() {
+      let mut chunk = [0; 1024];
+      let len = self.inner.read(&mut chunk)?;
+      if len == 0 {
+        self.done = true;
+        self.decoder.finish(&mut self.pending).map_err(invalid)?;
+      }
+      for c in &chunk[..len] {
+        self.decoder.push(*c, &mut self.pending).map_err(invalid)?;
+      }
+    }
+    let len = buf.len().min(self.pending.len());
+    buf[..len].copy_from_slice(&self.pending[..len]);
+    self.pending.drain(..len);
+    Ok(len)
+  }
+}
@@ -0,0 +1,144 @@
+use super::{BadEncodingError, BadEncodingErrorKind, Encoding};
+
+const Z85_SYMBOLS: &[u8; 85] =
+  b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+// 4 bytes to 5 symbols
+const GROUP_LEN: usize = 5;
+
+// both encode 4 bytes as 5 base 85 digits. A last group of n < 4 bytes is padded with zeros and
+// cut to n + 1 symbols, the Ascii85 rule that Z85 doesn't define
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+  // Adobe flavour, digits from '!', z for a group of zeros and optional <~ ~> delimiters
+  #[default]
+  Ascii85,
+  // ZeroMQ flavour, safe inside source code strings
+  Z85,
+}
+
+impl Variant {
+  fn symbol(&self, digit: u32) -> u8 {
+    match self {
+      Variant::Ascii85 => b'!' + digit as u8,
+      Variant::Z85 => Z85_SYMBOLS[digit as usize],
+    }
+  }
+
+  fn digit(&self, c: u8) -> Option<u32> {
+    match self {
+      Variant::Ascii85 => (b'!'..=b'u').contains(&c).then(|| u32::from(c - b'!')),
+      Variant::Z85 => Z85_SYMBOLS.iter().position(|v| *v == c).map(|v| v as u32),
+    }
+  }
+
+  pub fn encode(&self, bytes: &[u8]) -> String {
+    let mut res = Vec::with_capacity(bytes.len().div_ceil(4) * GROUP_LEN);
+    for chunk in bytes.chunks(4) {
+      let mut buf = [0; 4];
+      buf[..chunk.len()].copy_from_slice(chunk);
+      let mut n = u32::from_be_bytes(buf);
+      if *self == Variant::Ascii85 && chunk.len() == 4 && n == 0 {
+        res.push(b'z');
+        continue;
+      }
+      let mut group = [0; GROUP_LEN];
+      for c in group.iter_mut().rev() {
+        *c = self.symbol(n % 85);
+        n /= 85;
+      }
+      res.extend_from_slice(&group[..chunk.len() + 1]);
+    }
+    String::from_utf8(res).unwrap()
+  }
+
+  // whitespace between symbols is skipped
+  pub fn decode(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    let error = |error_kind, position, c: u8| BadEncodingError {
+      encoding: self.name(),
+      error_kind,
+      character: char::from(c),
+      position,
+    };
+    let (start, end) = match self {
+      Variant::Ascii85 => {
+        delimiters(text).map_err(|p| error(BadEncodingErrorKind::BadArmor, p, 0))?
+      }
+      Variant::Z85 => (0, text.len()),
+    };
+    let mut res = Vec::with_capacity(text.len() / GROUP_LEN * 4);
+    let mut group: Vec<u32> = Vec::with_capacity(GROUP_LEN);
+    let mut last = start;
+    for (position, c) in text.iter().enumerate().take(end).skip(start) {
+      if c.is_ascii_whitespace() {
+        continue;
+      }
+      if *self == Variant::Ascii85 && *c == b'z' && group.is_empty() {
+        res.extend_from_slice(&[0; 4]);
+        continue;
+      }
+      let digit =
+        self.digit(*c).ok_or_else(|| error(BadEncodingErrorKind::BadChar, position, *c))?;
+      group.push(digit);
+      last = position;
+      if group.len() == GROUP_LEN {
+        let n =
+          group_value(&group).ok_or_else(|| error(BadEncodingErrorKind::Overflow, position, *c))?;
+        res.extend_from_slice(&n.to_be_bytes());
+        group.clear();
+      }
+    }
+    match group.len() {
+      0 => {}
+      1 => return Err(error(BadEncodingErrorKind::BadSize, last, text[last])),
+      len => {
+        // padded with the highest digit so that the cut bytes round down
+        group.resize(GROUP_LEN, 84);
+        let n = group_value(&group)
+          .ok_or_else(|| error(BadEncodingErrorKind::Overflow, last, text[last]))?;
+        res.extend_from_slice(&n.to_be_bytes()[..len - 1]);
+      }
+    }
+    Ok(res)
+  }
+}
+
+fn group_value(group: &[u32]) -> Option<u32> {
+  let n = group.iter().fold(0u64, |acc, v| acc * 85 + u64::from(*v));
+  u32::try_from(n).ok()
+}
+
+// the range inside the optional <~ ~> delimiters, or the position of the bad delimiter
+fn delimiters(text: &[u8]) -> Result<(usize, usize), usize> {
+  let first = text.iter().position(|c| !c.is_ascii_whitespace()).unwrap_or(text.len());
+  let opened = text[first..].starts_with(b"<~");
+  let close = text.windows(2).position(|v| v == b"~>");
+  match (opened, close) {
+    (true, Some(close)) if close >= first + 2 => {
+      let trailing = text[close + 2..].iter().position(|c| !c.is_ascii_whitespace());
+      match trailing {
+        Some(p) => Err(close + 2 + p),
+        None => Ok((first + 2, close)),
+      }
+    }
+    (true, _) => Err(first),
+    (false, Some(close)) => Err(close),
+    (false, None) => Ok((0, text.len())),
+  }
+}
+
+impl Encoding for Variant {
+  fn name(&self) -> &'static str {
+    match self {
+      Variant::Ascii85 => "ascii85",
+      Variant::Z85 => "z85",
+    }
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    self.encode(bytes)
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    self.decode(text)
+  }
+}
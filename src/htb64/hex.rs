@@ -0,0 +1,265 @@
+use std::io::{self, Read, Write};
+
+use super::{BadEncodingError, BadHexError, BadHexErrorKind, Encoding};
+
+const LOWER_SYMBOLS: &[u8; 16] = b"0123456789abcdef";
+const UPPER_SYMBOLS: &[u8; 16] = b"0123456789ABCDEF";
+const DUMP_WIDTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+  #[default]
+  Lower,
+  Upper,
+}
+
+impl Case {
+  fn symbols(&self) -> &'static [u8; 16] {
+    match self {
+      Case::Lower => LOWER_SYMBOLS,
+      Case::Upper => UPPER_SYMBOLS,
+    }
+  }
+}
+
+// lowercase
+pub fn encode<T: AsRef<[u8]>>(bytes: T) -> String {
+  encode_with(bytes, Case::Lower)
+}
+
+pub fn encode_with<T: AsRef<[u8]>>(bytes: T, case: Case) -> String {
+  let bytes = bytes.as_ref();
+  let mut res = vec![0; bytes.len() * 2];
+  encode_to_slice(bytes, &mut res, case).unwrap();
+  String::from_utf8(res).unwrap()
+}
+
+// the hex string written at the start of out, which must hold twice as many bytes as the input
+pub fn encode_to_slice<'a>(
+  bytes: &[u8],
+  out: &'a mut [u8],
+  case: Case,
+) -> Result<&'a str, BadHexError> {
+  let Some(out) = out.get_mut(..bytes.len() * 2) else {
+    return Err(BadHexError {
+      error_kind: BadHexErrorKind::SmallBuffer,
+      position: bytes.len() * 2,
+      character: '\0',
+    });
+  };
+  let symbols = case.symbols();
+  for (b, pair) in bytes.iter().zip(out.chunks_exact_mut(2)) {
+    pair[0] = symbols[(b >> 4) as usize];
+    pair[1] = symbols[(b & 0x0f) as usize];
+  }
+  Ok(std::str::from_utf8(out).unwrap())
+}
+
+// exactly two hex digits per byte, either case
+pub fn decode<T: AsRef<[u8]>>(text: T) -> Result<Vec<u8>, BadHexError> {
+  let text = text.as_ref();
+  let mut decoder = Decoder::new(false);
+  let mut res = Vec::with_capacity(text.len() / 2);
+  text.iter().try_for_each(|c| decoder.push(*c, &mut res))?;
+  decoder.finish()?;
+  Ok(res)
+}
+
+// like decode but whitespace, ':' and ',' separators and 0x prefixes are skipped, so that
+// "0x4a 0x6f", "4a:6f" and "4A6F\n" are all accepted
+pub fn decode_tolerant<T: AsRef<[u8]>>(text: T) -> Result<Vec<u8>, BadHexError> {
+  let text = text.as_ref();
+  let mut decoder = Decoder::new(true);
+  let mut res = Vec::with_capacity(text.len() / 2);
+  text.iter().try_for_each(|c| decoder.push(*c, &mut res))?;
+  decoder.finish()?;
+  Ok(res)
+}
+
+// hex as an Encoding, parsed with decode_tolerant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hex(pub Case);
+
+impl Encoding for Hex {
+  fn name(&self) -> &'static str {
+    "hex"
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    encode_with(bytes, self.0)
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    Ok(decode_tolerant(text)?)
+  }
+}
+
+// pairs up hex digits, a byte split across two chunks of a stream is finished by the next one
+#[derive(Debug, Clone)]
+struct Decoder {
+  tolerant: bool,
+  // the first digit of the byte being read, with its position
+  high: Option<(u8, usize)>,
+  position: usize,
+  // a 0 read at the start of a token, maybe the start of 0x
+  zero: Option<usize>,
+  token_start: bool,
+}
+
+impl Decoder {
+  fn new(tolerant: bool) -> Decoder {
+    Decoder { tolerant, high: None, position: 0, zero: None, token_start: true }
+  }
+
+  fn push(&mut self, c: u8, out: &mut Vec<u8>) -> Result<(), BadHexError> {
+    let position = self.position;
+    self.position += 1;
+    if self.tolerant {
+      if let Some(zero) = self.zero.take() {
+        if c == b'x' || c == b'X' {
+          return Ok(());
+        }
+        self.digit(b'0', zero, out)?;
+      }
+      if c.is_ascii_whitespace() || c == b':' || c == b',' {
+        self.token_start = true;
+        return Ok(());
+      }
+      if self.token_start && c == b'0' && self.high.is_none() {
+        self.token_start = false;
+        self.zero = Some(position);
+        return Ok(());
+      }
+      self.token_start = false;
+    }
+    self.digit(c, position, out)
+  }
+
+  fn digit(&mut self, c: u8, position: usize, out: &mut Vec<u8>) -> Result<(), BadHexError> {
+    let value = match c {
+      b'0'..=b'9' => c - b'0',
+      b'a'..=b'f' => c - b'a' + 10,
+      b'A'..=b'F' => c - b'A' + 10,
+      _ => {
+        return Err(BadHexError {
+          error_kind: BadHexErrorKind::BadChar,
+          character: char::from(c),
+          position,
+        });
+      }
+    };
+    match self.high.take() {
+      Some((high, _)) => out.push(high << 4 | value),
+      None => self.high = Some((value, position)),
+    }
+    Ok(())
+  }
+
+  // a lone digit left at the end is an odd length
+  fn finish(&mut self) -> Result<(), BadHexError> {
+    if let Some(zero) = self.zero.take() {
+      self.digit(b'0', zero, &mut vec![])?;
+    }
+    match self.high {
+      Some((_, position)) => {
+        Err(BadHexError { error_kind: BadHexErrorKind::BadSize, position, character: '\0' })
+      }
+      None => Ok(()),
+    }
+  }
+}
+
+// encodes everything written to it as hex into the inner writer
+#[derive(Debug)]
+pub struct HexWriter<W: Write> {
+  inner: W,
+  case: Case,
+}
+
+impl<W: Write> HexWriter<W> {
+  pub fn new(inner: W, case: Case) -> HexWriter<W> {
+    HexWriter { inner, case }
+  }
+
+  pub fn into_inner(self) -> W {
+    self.inner
+  }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let mut out = [0; 1024];
+    let len = buf.len().min(out.len() / 2);
+    let hex = encode_to_slice(&buf[..len], &mut out, self.case).unwrap();
+    self.inner.write_all(hex.as_bytes())?;
+    Ok(len)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+// decodes the hex text read from the inner reader, bad input is an InvalidData error
+#[derive(Debug)]
+pub struct HexReader<R: Read> {
+  inner: R,
+  decoder: Decoder,
+  // decoded bytes not returned yet
+  pending: Vec<u8>,
+  done: bool,
+}
+
+impl<R: Read> HexReader<R> {
+  pub fn new(inner: R) -> HexReader<R> {
+    HexReader { inner, decoder: Decoder::new(false), pending: vec![], done: false }
+  }
+
+  // skips the separators and prefixes decode_tolerant skips
+  pub fn tolerant(inner: R) -> HexReader<R> {
+    HexReader { inner, decoder: Decoder::new(true), pending: vec![], done: false }
+  }
+}
+
+impl<R: Read> Read for HexReader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let invalid = |err: BadHexError| io::Error::new(io::ErrorKind::InvalidData, err.to_string());
+    while self.pending.is_empty() && !self.done && !buf.is_empty() {
+      let mut chunk = [0; 1024];
+      let len = self.inner.read(&mut chunk)?;
+      if len == 0 {
+        self.done = true;
+        self.decoder.finish().map_err(invalid)?;
+      }
+      for c in &chunk[..len] {
+        self.decoder.push(*c, &mut self.pending).map_err(invalid)?;
+      }
+    }
+    let len = buf.len().min(self.pending.len());
+    buf[..len].copy_from_slice(&self.pending[..len]);
+    self.pending.drain(..len);
+    Ok(len)
+  }
+}
+
+// xxd style dump: offset, 16 bytes in groups of two and the printable ASCII
+pub fn hexdump(bytes: &[u8]) -> String {
+  let mut res = String::new();
+  for (idx, line) in bytes.chunks(DUMP_WIDTH).enumerate() {
+    let mut groups = String::new();
+    for (i, pair) in line.chunks(2).enumerate() {
+      if i > 0 {
+        groups.push(' ');
+      }
+      groups.push_str(&encode(pair));
+    }
+    let ascii: String = line
+      .iter()
+      .map(|c| if c.is_ascii_graphic() || *c == b' ' { *c as char } else { '.' })
+      .collect();
+    // 2 digits per byte and a space between groups
+    let width = DUMP_WIDTH * 2 + DUMP_WIDTH / 2 - 1;
+    res.push_str(&format!("{:08x}: {groups:<width$}  {ascii}\n", idx * DUMP_WIDTH));
+  }
+  res
+}
@@ -0,0 +1,102 @@
+use super::{BadEncodingError, BadEncodingErrorKind, Encoding, base64};
+
+// RFC 7468 lines of the base64 body
+const LINE_LEN: usize = 64;
+
+// one -----BEGIN label----- ... -----END label----- block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem {
+  pub label: String,
+  pub data: Vec<u8>,
+}
+
+impl Pem {
+  pub fn new(label: &str, data: &[u8]) -> Pem {
+    Pem { label: label.to_owned(), data: data.to_owned() }
+  }
+
+  pub fn encode(&self) -> String {
+    let body = base64::STANDARD.encode(&self.data);
+    let mut res = format!("-----BEGIN {}-----\n", self.label);
+    for line in body.as_bytes().chunks(LINE_LEN) {
+      res.push_str(std::str::from_utf8(line).unwrap());
+      res.push('\n');
+    }
+    res.push_str(&format!("-----END {}-----\n", self.label));
+    res
+  }
+
+  // every block of the text, the text around them is ignored. Positions of errors are offsets in
+  // the whole text
+  pub fn parse_all(text: &[u8]) -> Result<Vec<Pem>, BadEncodingError> {
+    let error = |position| BadEncodingError {
+      encoding: "pem",
+      error_kind: BadEncodingErrorKind::BadArmor,
+      character: '\0',
+      position,
+    };
+    let mut res = vec![];
+    let mut offset = 0;
+    while let Some(begin) = find(text, b"-----BEGIN ", offset) {
+      let label_start = begin + b"-----BEGIN ".len();
+      let label_end = find(text, b"-----", label_start).ok_or_else(|| error(begin))?;
+      let label = &text[label_start..label_end];
+      if label.contains(&b'\n') {
+        return Err(error(begin));
+      }
+      let body_start = label_end + b"-----".len();
+      let end_line = [b"-----END ", label, b"-----"].concat();
+      let body_end = find(text, &end_line, body_start).ok_or_else(|| error(begin))?;
+      let data = base64::STANDARD.decode_lenient(&text[body_start..body_end]).map_err(|err| {
+        let mut err = BadEncodingError::from(err);
+        err.position += body_start;
+        err
+      })?;
+      let label = String::from_utf8(label.to_owned()).map_err(|_| error(label_start))?;
+      res.push(Pem { label, data });
+      offset = body_end + end_line.len();
+    }
+    Ok(res)
+  }
+}
+
+fn find(text: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+  text[from..].windows(needle.len()).position(|v| v == needle).map(|v| v + from)
+}
+
+// PEM as an Encoding, writing one block under label and reading the first block with that label,
+// or the first block at all when the label is empty
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Armor {
+  pub label: String,
+}
+
+impl Armor {
+  pub fn new(label: &str) -> Armor {
+    Armor { label: label.to_owned() }
+  }
+}
+
+impl Encoding for Armor {
+  fn name(&self) -> &'static str {
+    "pem"
+  }
+
+  fn to_text(&self, bytes: &[u8]) -> String {
+    let label = if self.label.is_empty() { "MESSAGE" } else { &self.label };
+    Pem::new(label, bytes).encode()
+  }
+
+  fn parse(&self, text: &[u8]) -> Result<Vec<u8>, BadEncodingError> {
+    Pem::parse_all(text)?
+      .into_iter()
+      .find(|v| self.label.is_empty() || v.label == self.label)
+      .map(|v| v.data)
+      .ok_or(BadEncodingError {
+        encoding: "pem",
+        error_kind: BadEncodingErrorKind::BadArmor,
+        character: '\0',
+        position: 0,
+      })
+  }
+}
@@ -4,3 +4,15 @@ pub mod distance;
 pub mod cryptog;
 pub mod oracle;
 pub mod oracle_hacker;
+pub mod bignum;
+pub mod crib_drag;
+pub mod dlog;
+pub mod dsa;
+pub mod ec;
+pub mod gcm;
+pub mod gf128;
+pub mod gf2;
+pub mod md_hash;
+pub mod rsa;
+pub mod scoring;
+pub mod xor_attack;
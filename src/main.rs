@@ -1,11 +1,8 @@
-use std::env;
+use std::{env, fs};
+
+use cryptopals_challeges_rs::htb64;
 
 mod challenges;
-mod cryptog;
-mod htb64;
-mod oracle;
-mod oracle_hacker;
-mod xor;
 
 fn main() {
   let challenges_vec: Vec<fn()> = vec![
@@ -32,9 +29,13 @@ fn main() {
   ];
 
   let args:Vec<String> = env::args().collect();
+  if args.len() == 4 && (args[1] == "decode" || args[1] == "encode") {
+    convert(&args[1], &args[2], &args[3]);
+    return;
+  }
   let challenge = if args.len() == 1 || args.len() > 2 {
     challenges_vec.len() as i32
-  } else if args[1].chars().all(|v| v.is_digit(10)) {
+  } else if args[1].chars().all(|v| v.is_ascii_digit()) {
     args[1].parse::<i32>().unwrap()
   } else {
     println!("Please specify a number from {} to {} for the challenge",1,challenges_vec.len());
@@ -50,3 +51,23 @@ fn main() {
   challenges_vec[challenge as usize - 1]();
   println!("{}","-".repeat(line.len()));
 }
+
+// decode prints a hexdump of the decoded file, encode prints the file in the encoding
+fn convert(direction: &str, encoding_name: &str, path: &str) {
+  let Some(encoding) = htb64::encoding_by_name(encoding_name) else {
+    println!("Unknown encoding {:?}, use one of: {}",encoding_name,htb64::ENCODING_NAMES.join(", "));
+    return;
+  };
+  let res = if direction == "decode" {
+    htb64::read_encoded(path,encoding.as_ref()).map(|v| htb64::hex::hexdump(&v))
+  } else {
+    fs::read(path).map(|v| {
+      let text = encoding.to_text(&v);
+      if text.ends_with('\n') { text } else { text + "\n" }
+    })
+  };
+  match res {
+    Ok(v) => print!("{}",v),
+    Err(err) => println!("Failed to {} {:?}: {}",direction,path,err),
+  }
+}
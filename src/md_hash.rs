@@ -0,0 +1,381 @@
+use std::{
+  cell::Cell,
+  collections::HashMap,
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Write},
+};
+
+use itertools::Itertools;
+
+use crate::{cryptog::aes_128_ecb_encrypt, htb64::hex};
+
+pub const BLOCK_SIZE: usize = 16;
+
+// Merkle-Damgård over a truncated AES: the state is the key, the message block the plaintext
+#[derive(Debug)]
+pub struct ToyHash {
+  bits: u32,
+  initial_state: u32,
+  compressions: Cell<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+  pub first: Vec<u8>,
+  pub second: Vec<u8>,
+  pub state: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionStats {
+  // single block collisions generated in the cheap function
+  pub collisions: usize,
+  // messages of the multicollision that went through the expensive function
+  pub candidates: usize,
+  pub cheap_compressions: u64,
+  pub expensive_compressions: u64,
+}
+
+impl ToyHash {
+  pub fn new(bits: u32) -> ToyHash {
+    ToyHash::with_initial_state(bits, 0x6a09_e667)
+  }
+
+  pub fn with_initial_state(bits: u32, initial_state: u32) -> ToyHash {
+    assert!((16..=32).contains(&bits), "The toy hash state must be 16 to 32 bits");
+    let initial_state = truncate(initial_state, bits);
+    ToyHash { bits, initial_state, compressions: Cell::new(0) }
+  }
+
+  pub fn bits(&self) -> u32 {
+    self.bits
+  }
+
+  pub fn initial_state(&self) -> u32 {
+    self.initial_state
+  }
+
+  // number of calls to the compression function so far
+  pub fn compressions(&self) -> u64 {
+    self.compressions.get()
+  }
+
+  pub fn compress(&self, state: u32, block: &[u8]) -> u32 {
+    assert!(block.len() == BLOCK_SIZE, "The compression function takes exactly one block");
+    self.compressions.set(self.compressions.get() + 1);
+    let mut key = state.to_be_bytes().to_vec();
+    key.resize(BLOCK_SIZE, 0);
+    let out = aes_128_ecb_encrypt(&key, block).unwrap();
+    truncate(u32::from_be_bytes(out[..4].try_into().unwrap()), self.bits)
+  }
+
+  // chains whole blocks without any padding
+  pub fn compress_blocks(&self, state: u32, blocks: &[u8]) -> u32 {
+    blocks.chunks(BLOCK_SIZE).fold(state, |state, block| self.compress(state, block))
+  }
+
+  pub fn hash(&self, message: &[u8]) -> u32 {
+    let mut padded = message.to_owned();
+    padded.extend(md_padding(message.len()));
+    self.compress_blocks(self.initial_state, &padded)
+  }
+}
+
+fn truncate(state: u32, bits: u32) -> u32 {
+  state >> (32 - bits)
+}
+
+// MD strengthening: 0x80, zeros, then the message length in bits as a 64-bit big endian number
+pub fn md_padding(message_len: usize) -> Vec<u8> {
+  let mut res = vec![0x80];
+  while !(message_len + res.len() + 8).is_multiple_of(BLOCK_SIZE) {
+    res.push(0);
+  }
+  res.extend((message_len as u64 * 8).to_be_bytes());
+  res
+}
+
+// birthday search for two different blocks taking `state` to the same next state
+pub fn find_collision(hash: &ToyHash, state: u32) -> Collision {
+  let mut seen: HashMap<u32, Vec<u8>> = HashMap::new();
+  loop {
+    let block: Vec<u8> = rand::random_iter().take(BLOCK_SIZE).collect();
+    let next = hash.compress(state, &block);
+    match seen.get(&next) {
+      Some(other) if *other != block => {
+        return Collision { first: other.to_owned(), second: block, state: next };
+      }
+      _ => {
+        seen.insert(next, block);
+      }
+    }
+  }
+}
+
+// Joux: n successive single block collisions give 2^n messages of n blocks with the same hash
+pub fn joux_multicollision(hash: &ToyHash, n: usize) -> Vec<Collision> {
+  let mut state = hash.initial_state();
+  (0..n)
+    .map(|_| {
+      let collision = find_collision(hash, state);
+      state = collision.state;
+      collision
+    })
+    .collect()
+}
+
+// message number `idx` of the multicollision, bit i picks the block for collision i
+pub fn multicollision_message(collisions: &[Collision], idx: usize) -> Vec<u8> {
+  collisions
+    .iter()
+    .enumerate()
+    .flat_map(|(i, collision)| {
+      let bit = (idx >> (collisions.len() - 1 - i)) & 1;
+      if bit == 0 { collision.first.clone() } else { collision.second.clone() }
+    })
+    .collect()
+}
+
+// h(x) = cheap(x) || expensive(x) is no stronger than the expensive function alone: build a
+// 2^(b/2) multicollision in the cheap function and look for a birthday collision among its
+// messages in the expensive one, adding one more cheap collision whenever that fails
+pub fn concatenated_collision(
+  cheap: &ToyHash,
+  expensive: &ToyHash,
+) -> (Vec<u8>, Vec<u8>, CollisionStats) {
+  let (cheap_start, expensive_start) = (cheap.compressions(), expensive.compressions());
+  let extend = |states: &[u32], collision: &Collision| -> Vec<u32> {
+    let next = |v: &u32| [&collision.first, &collision.second].map(|b| expensive.compress(*v, b));
+    states.iter().flat_map(next).collect()
+  };
+  let mut collisions = joux_multicollision(cheap, expensive.bits() as usize / 2);
+  // expensive states for every message of the multicollision, in message index order
+  let mut states = vec![expensive.initial_state()];
+  for collision in collisions.iter() {
+    states = extend(&states, collision);
+  }
+  let mut candidates = 0;
+  loop {
+    let padding = md_padding(collisions.len() * BLOCK_SIZE);
+    let mut seen: HashMap<u32, usize> = HashMap::new();
+    for (idx, state) in states.iter().enumerate() {
+      candidates += 1;
+      let digest = expensive.compress_blocks(*state, &padding);
+      if let Some(other) = seen.insert(digest, idx) {
+        let stats = CollisionStats {
+          collisions: collisions.len(),
+          candidates,
+          cheap_compressions: cheap.compressions() - cheap_start,
+          expensive_compressions: expensive.compressions() - expensive_start,
+        };
+        return (
+          multicollision_message(&collisions, other),
+          multicollision_message(&collisions, idx),
+          stats,
+        );
+      }
+    }
+    let collision = find_collision(cheap, collisions.last().unwrap().state);
+    states = extend(&states, &collision);
+    collisions.push(collision);
+  }
+}
+
+// k collisions between a single block and 2^(k-1-i) + 1 blocks, any length of k to k + 2^k - 1
+// blocks can be put together and always lands on `state`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandableMessage {
+  pub pieces: Vec<(Vec<u8>, Vec<u8>)>,
+  pub state: u32,
+}
+
+impl ExpandableMessage {
+  pub fn min_blocks(&self) -> usize {
+    self.pieces.len()
+  }
+
+  pub fn max_blocks(&self) -> usize {
+    self.pieces.len() + (1 << self.pieces.len()) - 1
+  }
+
+  pub fn produce(&self, blocks: usize) -> Vec<u8> {
+    assert!((self.min_blocks()..=self.max_blocks()).contains(&blocks), "Length out of range");
+    let extra = blocks - self.min_blocks();
+    self
+      .pieces
+      .iter()
+      .enumerate()
+      .flat_map(|(i, (short, long))| {
+        let bit = (extra >> (self.pieces.len() - 1 - i)) & 1;
+        if bit == 0 { short.clone() } else { long.clone() }
+      })
+      .collect()
+  }
+}
+
+// birthday search for blocks taking two different states to the same next state
+pub fn find_collision_between(hash: &ToyHash, first: u32, second: u32) -> Collision {
+  let mut seen_first: HashMap<u32, Vec<u8>> = HashMap::new();
+  let mut seen_second: HashMap<u32, Vec<u8>> = HashMap::new();
+  loop {
+    let block: Vec<u8> = rand::random_iter().take(BLOCK_SIZE).collect();
+    let next = hash.compress(first, &block);
+    if let Some(other) = seen_second.get(&next) {
+      return Collision { first: block, second: other.to_owned(), state: next };
+    }
+    seen_first.insert(next, block.clone());
+    let next = hash.compress(second, &block);
+    if let Some(other) = seen_first.get(&next) {
+      return Collision { first: other.to_owned(), second: block, state: next };
+    }
+    seen_second.insert(next, block);
+  }
+}
+
+pub fn expandable_message(hash: &ToyHash, state: u32, k: usize) -> ExpandableMessage {
+  let mut state = state;
+  let pieces = (0..k)
+    .map(|i| {
+      let dummy = vec![0u8; BLOCK_SIZE << (k - 1 - i)];
+      let collision = find_collision_between(hash, state, hash.compress_blocks(state, &dummy));
+      state = collision.state;
+      let mut long = dummy;
+      long.extend(collision.second);
+      (collision.first, long)
+    })
+    .collect();
+  ExpandableMessage { pieces, state }
+}
+
+// Kelsey-Schneier: an expandable message followed by a bridge block into one of the chaining
+// values of the target, padded out to the same length so the final length block matches too
+pub fn second_preimage(hash: &ToyHash, message: &[u8]) -> Vec<u8> {
+  assert!(message.len().is_multiple_of(BLOCK_SIZE), "The target must be whole blocks");
+  let blocks = message.len() / BLOCK_SIZE;
+  assert!(blocks >= 4, "The target is too short to expand into");
+  let k = blocks.ilog2() as usize;
+  let expandable = expandable_message(hash, hash.initial_state(), k);
+  // chaining value after i blocks, only those the expandable message can reach
+  let mut chaining: HashMap<u32, usize> = HashMap::new();
+  let mut state = hash.initial_state();
+  for (i, block) in message.chunks(BLOCK_SIZE).enumerate() {
+    state = hash.compress(state, block);
+    if (expandable.min_blocks() + 1..=expandable.max_blocks() + 1).contains(&(i + 1)) {
+      chaining.insert(state, i + 1);
+    }
+  }
+  loop {
+    let bridge: Vec<u8> = rand::random_iter().take(BLOCK_SIZE).collect();
+    if let Some(idx) = chaining.get(&hash.compress(expandable.state, &bridge)) {
+      let mut res = expandable.produce(idx - 1);
+      res.extend(bridge);
+      res.extend_from_slice(&message[idx * BLOCK_SIZE..]);
+      return res;
+    }
+  }
+}
+
+// collision tree for the herding attack: 2^k leaf states funnelled pairwise into one root.
+// blocks[l][i] takes node i of level l to node i / 2 of level l + 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diamond {
+  pub bits: u32,
+  pub leaves: Vec<u32>,
+  pub blocks: Vec<Vec<Vec<u8>>>,
+  pub root: u32,
+}
+
+impl Diamond {
+  pub fn build(hash: &ToyHash, k: usize) -> Diamond {
+    let mut leaves: Vec<u32> = vec![];
+    while leaves.len() < 1 << k {
+      let leaf = rand::random_range(0..1u64 << hash.bits()) as u32;
+      if !leaves.contains(&leaf) {
+        leaves.push(leaf);
+      }
+    }
+    let mut states = leaves.clone();
+    let mut blocks = vec![];
+    while states.len() > 1 {
+      let collisions: Vec<Collision> =
+        states.chunks(2).map(|v| find_collision_between(hash, v[0], v[1])).collect();
+      states = collisions.iter().map(|v| v.state).collect();
+      blocks.push(collisions.into_iter().flat_map(|v| [v.first, v.second]).collect());
+    }
+    Diamond { bits: hash.bits(), leaves, blocks, root: states[0] }
+  }
+
+  // the hash to publish before the prefix is known, which has to fit in `prefix_blocks`
+  pub fn commit(&self, hash: &ToyHash, prefix_blocks: usize) -> u32 {
+    hash.compress_blocks(self.root, &md_padding(self.message_len(prefix_blocks)))
+  }
+
+  // prefix padded with spaces, a linking block into one of the leaves, then the path to the root
+  pub fn herd(&self, hash: &ToyHash, prefix: &[u8], prefix_blocks: usize) -> Vec<u8> {
+    assert!(hash.bits() == self.bits, "The diamond was built for another hash size");
+    assert!(prefix.len() <= prefix_blocks * BLOCK_SIZE, "The prefix is longer than committed");
+    let mut res = prefix.to_owned();
+    res.resize(prefix_blocks * BLOCK_SIZE, b' ');
+    let state = hash.compress_blocks(hash.initial_state(), &res);
+    let leaves: HashMap<u32, usize> =
+      self.leaves.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+    let (link, mut idx) = loop {
+      let link: Vec<u8> = rand::random_iter().take(BLOCK_SIZE).collect();
+      if let Some(idx) = leaves.get(&hash.compress(state, &link)) {
+        break (link, *idx);
+      }
+    };
+    res.extend(link);
+    for level in self.blocks.iter() {
+      res.extend_from_slice(&level[idx]);
+      idx /= 2;
+    }
+    assert!(res.len() == self.message_len(prefix_blocks));
+    res
+  }
+
+  fn message_len(&self, prefix_blocks: usize) -> usize {
+    (prefix_blocks + 1 + self.blocks.len()) * BLOCK_SIZE
+  }
+
+  // hash size and root, the leaves, then one line of blocks per level, all in hex
+  pub fn save(&self, path: &str) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "{} {:08x}", self.bits, self.root)?;
+    writeln!(file, "{}", self.leaves.iter().map(|v| format!("{v:08x}")).join(" "))?;
+    for level in self.blocks.iter() {
+      writeln!(file, "{}", level.iter().map(hex::encode).join(" "))?;
+    }
+    file.flush()
+  }
+
+  pub fn load(path: &str) -> io::Result<Diamond> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+    let lines: Vec<String> = BufReader::new(File::open(path)?).lines().collect::<Result<_, _>>()?;
+    let (header, levels) = lines.split_at_checked(2).ok_or_else(|| invalid("Truncated diamond"))?;
+    let (bits, root) = header[0].split_once(' ').ok_or_else(|| invalid("Invalid header"))?;
+    let bits = bits.parse().map_err(|_| invalid("Invalid hash size"))?;
+    let root = u32::from_str_radix(root, 16).map_err(|_| invalid("Invalid root state"))?;
+    let leaves = header[1]
+      .split_whitespace()
+      .map(|v| u32::from_str_radix(v, 16))
+      .collect::<Result<Vec<u32>, _>>()
+      .map_err(|_| invalid("Invalid leaf state"))?;
+    let blocks = levels
+      .iter()
+      .map(|line| {
+        line.split_whitespace().map(|v| hex::decode(v.as_bytes())).collect::<Result<Vec<_>, _>>()
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|_| invalid("Invalid block"))?;
+    // more levels than a usize can count leaves for can't match either
+    let leaf_count = u32::try_from(blocks.len()).ok().and_then(|v| 1usize.checked_shl(v));
+    let shape_ok = leaf_count == Some(leaves.len())
+      && blocks.iter().enumerate().all(|(l, level)| level.len() == leaves.len() >> l)
+      && blocks.iter().flatten().all(|v| v.len() == BLOCK_SIZE);
+    if !shape_ok {
+      return Err(invalid("Malformed diamond"));
+    }
+    Ok(Diamond { bits, leaves, blocks, root })
+  }
+}
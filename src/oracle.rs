@@ -1,17 +1,31 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Write, sync::LazyLock};
 
-use base64::{Engine, prelude::BASE64_STANDARD};
-use openssl::error::ErrorStack;
+use flate2::{Compression, write::DeflateEncoder};
+use openssl::{bn::BigNumRef, error::ErrorStack};
 use rand::{Rng, seq::IndexedRandom};
 
 use crate::{
+  bignum::mod_exp,
   cryptog::{
     InvalidPadding, aes_128_ecb_decrypt, aes_128_ecb_encrypt, aes_cbc_decrypt_no_unpadding,
-    aes_cbc_encrypt, pkcs7_padding, undo_pkcs7_padding, validate_undo_pkcs7_padding,
-  }
+    aes_cbc_encrypt, aes_ctr, cbc_mac, cbc_mac_zero_iv, hmac_sha256, pkcs7_padding, rc4,
+    undo_pkcs7_padding, validate_undo_pkcs7_padding,
+  },
+  dlog::DhParams,
+  ec::{self, EcPoint, EcdhParams, MontgomeryParams},
+  htb64::base64,
+  rsa::{self, RsaPrivateKey},
 };
 
 #[derive(Debug, PartialEq, Eq)]
+pub struct Transfer {
+  pub from: i64,
+  pub to: i64,
+  pub amount: i64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum CookieValue {
   StringValue(String),
   NumberValue(i64),
@@ -44,8 +58,8 @@ pub fn encryption_oracle(plaintext: String) -> (Vec<u8>, String) {
 pub fn consistent_encryption_oracle(input_bytes: &[u8], oracle_key: &[u8]) -> Vec<u8> {
   let unknown_string_base64 = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
   let unknown_string = String::from_utf8(
-    BASE64_STANDARD
-      .decode(unknown_string_base64)
+    base64::STANDARD
+      .decode(unknown_string_base64.as_bytes())
       .expect("Failed to decode unknown base 64 string for challenge 12"),
   )
   .expect("Failed to create string based on unknown string for challenge 12");
@@ -136,7 +150,7 @@ pub fn oracle_cbc_is_admin(
 }
 
 pub fn oracle_cbc_padding(oracle_key: &[u8]) -> (Vec<u8>, Vec<u8>) {
-  let choices = &vec![
+  let choices = &[
     "MDAwMDAwTm93IHRoYXQgdGhlIHBhcnR5IGlzIGp1bXBpbmc=",
     "MDAwMDAxV2l0aCB0aGUgYmFzcyBraWNrZWQgaW4gYW5kIHRoZSBWZWdhJ3MgYXJlIHB1bXBpbic=",
     "MDAwMDAyUXVpY2sgdG8gdGhlIHBvaW50LCB0byB0aGUgcG9pbnQsIG5vIGZha2luZw==",
@@ -148,7 +162,8 @@ pub fn oracle_cbc_padding(oracle_key: &[u8]) -> (Vec<u8>, Vec<u8>) {
     "MDAwMDA4b2xsaW4nIGluIG15IGZpdmUgcG9pbnQgb2g=",
     "MDAwMDA5aXRoIG15IHJhZy10b3AgZG93biBzbyBteSBoYWlyIGNhbiBibG93",
   ];
-  let choice = BASE64_STANDARD.decode(choices.choose(&mut rand::rng()).unwrap()).unwrap();
+  let choice =
+    base64::STANDARD.decode(choices.choose(&mut rand::rng()).unwrap().as_bytes()).unwrap();
   let iv: Vec<u8> = rand::rng().random_iter().take(oracle_key.len()).collect();
   let ciphertext = aes_cbc_encrypt(&iv, oracle_key, &choice).unwrap();
   (ciphertext, iv)
@@ -157,5 +172,160 @@ pub fn oracle_cbc_padding(oracle_key: &[u8]) -> (Vec<u8>, Vec<u8>) {
 pub fn oracle_cbc_padding_validator(token: &[u8], iv: &[u8], oracle_key: &[u8]) -> bool {
   let plaintext = aes_cbc_decrypt_no_unpadding(iv, oracle_key, token).unwrap();
   let plaintext = validate_undo_pkcs7_padding(&plaintext);
-  return plaintext.is_ok();
+  plaintext.is_ok()
+}
+
+#[allow(dead_code)]
+pub fn oracle_rsa_parity(ciphertext: &BigNumRef, private_key: &RsaPrivateKey) -> bool {
+  rsa::decrypt(private_key, ciphertext).unwrap().is_even()
+}
+
+#[allow(dead_code)]
+pub fn oracle_rsa_pkcs1_padding_validator(
+  ciphertext: &BigNumRef,
+  private_key: &RsaPrivateKey,
+) -> bool {
+  let k = rsa::modulus_len(&private_key.n);
+  let plaintext = rsa::decrypt(private_key, ciphertext).unwrap();
+  // the leading 00 is dropped by the big number, so 00 02 shows up as k - 1 bytes starting with 02
+  plaintext.num_bytes() as usize == k - 1 && plaintext.to_vec()[0] == 0x02
+}
+
+const COMPRESSION_SESSION_ID: &str = "TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=";
+
+fn compressed_request(plaintext: &[u8]) -> Vec<u8> {
+  let mut request = format!(
+    "POST / HTTP/1.1\r\nHost: hapless.com\r\nCookie: sessionid={}\r\nContent-Length: {}\r\n",
+    COMPRESSION_SESSION_ID,
+    plaintext.len()
+  )
+  .into_bytes();
+  request.extend_from_slice(plaintext);
+  let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+  encoder.write_all(&request).expect("Failed to compress request");
+  encoder.finish().expect("Failed to compress request")
+}
+
+#[allow(dead_code)]
+pub fn oracle_compression_ctr(plaintext: &[u8]) -> usize {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  aes_ctr(rand::random(), &key, &compressed_request(plaintext)).len()
+}
+
+#[allow(dead_code)]
+pub fn oracle_compression_cbc(plaintext: &[u8]) -> usize {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let iv: Vec<u8> = rand::random_iter().take(16).collect();
+  aes_cbc_encrypt(&iv, &key, &compressed_request(plaintext)).unwrap().len()
+}
+
+const RC4_COOKIE: &str = "QkUgU1VSRSBUTyBEUklOSyBZT1VSIE9WQUxUSU5F";
+
+// encrypts request || cookie under a fresh RC4 key for each request
+#[allow(dead_code)]
+pub fn oracle_rc4_cookie(request: &[u8]) -> Vec<u8> {
+  static COOKIE: LazyLock<Vec<u8>> =
+    LazyLock::new(|| base64::STANDARD.decode(RC4_COOKIE.as_bytes()).unwrap());
+  let key: [u8; 16] = rand::random();
+  let mut plaintext = request.to_owned();
+  plaintext.extend_from_slice(&COOKIE);
+  rc4(&key, &plaintext)
+}
+
+// the client only signs transfers out of the logged in account: message || IV || MAC
+#[allow(dead_code)]
+pub fn oracle_transfer_client(account: i64, to: i64, amount: i64, oracle_key: &[u8]) -> Vec<u8> {
+  let mut request = format!("from={account}&to={to}&amount={amount}").into_bytes();
+  let iv: Vec<u8> = rand::random_iter().take(oracle_key.len()).collect();
+  let mac = cbc_mac(&iv, oracle_key, &request).unwrap();
+  request.extend(iv);
+  request.extend(mac);
+  request
+}
+
+#[allow(dead_code)]
+pub fn oracle_transfer_server(request: &[u8], oracle_key: &[u8]) -> Option<Transfer> {
+  let block_size = oracle_key.len();
+  let (message, iv_mac) = request.split_at_checked(request.len().checked_sub(2 * block_size)?)?;
+  let (iv, mac) = iv_mac.split_at(block_size);
+  if cbc_mac(iv, oracle_key, message).ok()? != mac {
+    return None;
+  }
+  let fields = parse_cookie(String::from_utf8_lossy(message).into_owned());
+  let number = |name: &str| match fields.get(name) {
+    Some(CookieValue::NumberValue(v)) => Some(*v),
+    _ => None,
+  };
+  Some(Transfer { from: number("from")?, to: number("to")?, amount: number("amount")? })
+}
+
+// second version of the API with a fixed zero IV and several transactions: message || MAC
+#[allow(dead_code)]
+pub fn oracle_transfer_client_multi(
+  account: i64,
+  transactions: &[(i64, i64)],
+  oracle_key: &[u8],
+) -> Vec<u8> {
+  let tx_list: Vec<String> =
+    transactions.iter().map(|(to, amount)| format!("{to}:{amount}")).collect();
+  let tx_list = tx_list.join(";");
+  let mut request = format!("from={account}&tx_list={tx_list}").into_bytes();
+  let mac = cbc_mac_zero_iv(oracle_key, &request).unwrap();
+  request.extend(mac);
+  request
+}
+
+// transactions that don't parse are skipped rather than failing the whole request
+#[allow(dead_code)]
+pub fn oracle_transfer_server_multi(request: &[u8], oracle_key: &[u8]) -> Option<Vec<Transfer>> {
+  let (message, mac) = request.split_at_checked(request.len().checked_sub(oracle_key.len())?)?;
+  if cbc_mac_zero_iv(oracle_key, message).ok()? != mac {
+    return None;
+  }
+  let fields = parse_cookie(String::from_utf8_lossy(message).into_owned());
+  let Some(CookieValue::NumberValue(from)) = fields.get("from") else {
+    return None;
+  };
+  let tx_list = match fields.get("tx_list")? {
+    CookieValue::StringValue(v) => v.to_owned(),
+    CookieValue::NumberValue(v) => v.to_string(),
+    CookieValue::BoolValue(_) => return None,
+  };
+  Some(
+    tx_list
+      .split(";")
+      .filter_map(|tx| {
+        let (to, amount) = tx.split_once(":")?;
+        Some(Transfer { from: *from, to: to.parse().ok()?, amount: amount.parse().ok()? })
+      })
+      .collect(),
+  )
+}
+
+pub const ECDH_BOB_MESSAGE: &[u8] = b"crazy flamboyant for the rap enjoyment";
+
+// Bob answers any public key with a MAC under the shared key, without checking the point is on
+// the curve
+#[allow(dead_code)]
+pub fn oracle_ecdh_bob(public: &EcPoint, secret: &BigNumRef, params: &EcdhParams) -> Vec<u8> {
+  let shared = ec::shared_secret(&params.curve, secret, public).unwrap();
+  hmac_sha256(&ec::shared_key(&shared), ECDH_BOB_MESSAGE).unwrap()
+}
+
+// x-only Bob: the public key is a bare u coordinate, on the curve or not
+#[allow(dead_code)]
+pub fn oracle_ecdh_bob_x_only(
+  public: &BigNumRef,
+  secret: &BigNumRef,
+  params: &MontgomeryParams,
+) -> Vec<u8> {
+  let shared = params.curve.ladder(public, secret).unwrap();
+  hmac_sha256(&ec::shared_key_x_only(&shared), ECDH_BOB_MESSAGE).unwrap()
+}
+
+// Bob raises whatever he is sent to his secret, never checking it lies in the subgroup of order q
+#[allow(dead_code)]
+pub fn oracle_dh_bob(public: &BigNumRef, secret: &BigNumRef, params: &DhParams) -> Vec<u8> {
+  let shared = mod_exp(public, secret, &params.p).unwrap();
+  hmac_sha256(&shared.to_vec(), ECDH_BOB_MESSAGE).unwrap()
 }
@@ -1,6 +1,17 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::{cryptog, htb64};
+use itertools::Itertools;
+
+use openssl::bn::{BigNum, BigNumRef};
+
+use crate::{
+  bignum::{self, ceil_div, mod_exp, mod_mul},
+  cryptog,
+  dlog::{self, DhParams, Group, MultiplicativeGroup},
+  ec::{self, EcPoint, EcdhParams, MontgomeryParams, XzPoint},
+  htb64,
+  rsa::{self, RsaPrivateKey, RsaPublicKey},
+};
 
 pub fn detect_ecb(key_size: usize, ciphertext: &[u8]) -> bool {
   let ciphertext_hex = htb64::bytes_to_hex(ciphertext);
@@ -179,7 +190,7 @@ pub fn cbc_padding_attack(
   let mut current = blocks.next();
   let mut previous = blocks.next();
   let mut decrypted: Vec<Vec<u8>> = vec![];
-  while !previous.is_none() {
+  while previous.is_some() {
     let target_block = current.unwrap();
     let prev_block = previous.unwrap();
     // each block gets "decrypted" the same way
@@ -249,3 +260,632 @@ fn cbc_padding_attack_block(
   }
   decrypted
 }
+
+#[allow(dead_code)]
+pub fn rsa_parity_attack(
+  ciphertext: &BigNumRef,
+  public_key: &RsaPublicKey,
+  oracle_fn: impl Fn(&BigNumRef) -> bool,
+  mut progress_fn: impl FnMut(&[u8]),
+) -> Vec<u8> {
+  let n = &public_key.n;
+  let double = mod_exp(&BigNum::from_u32(2).unwrap(), &public_key.e, n).unwrap();
+  // the plaintext lies in [n * lower / 2^i, n * upper / 2^i]
+  // kept as integers so no precision is lost
+  let mut lower = BigNum::from_u32(0).unwrap();
+  let mut upper = BigNum::from_u32(1).unwrap();
+  let mut current = ciphertext.to_owned().unwrap();
+  for i in 1..=n.num_bits() {
+    current = mod_mul(&current, &double, n).unwrap();
+    lower = &lower << 1;
+    upper = &upper << 1;
+    if oracle_fn(&current) {
+      // 2^i * m did not wrap around the modulus an odd number of times
+      upper = &upper - &BigNum::from_u32(1).unwrap();
+    } else {
+      lower = &lower + &BigNum::from_u32(1).unwrap();
+    }
+    progress_fn(&(&(n * &upper) >> i).to_vec());
+  }
+  (&(n * &upper) >> n.num_bits()).to_vec()
+}
+
+#[allow(dead_code)]
+pub fn bleichenbacher_attack(
+  ciphertext: &BigNumRef,
+  public_key: &RsaPublicKey,
+  oracle_fn: impl Fn(&BigNumRef) -> bool,
+) -> Vec<u8> {
+  let n = &public_key.n;
+  let k = rsa::modulus_len(n);
+  let one = BigNum::from_u32(1).unwrap();
+  let b = &one << (8 * (k as i32 - 2));
+  let two_b = &b << 1;
+  let three_b = &two_b + &b;
+  let three_b_minus_one = &three_b - &one;
+  // c * s^e decrypts to m * s
+  let conforming = |s: &BigNumRef| {
+    let c = mod_mul(ciphertext, &mod_exp(s, &public_key.e, n).unwrap(), n).unwrap();
+    oracle_fn(&c)
+  };
+
+  // step 1 is skipped: the ciphertext given is already PKCS conforming so s0 = 1
+  let mut intervals: Vec<(BigNum, BigNum)> =
+    vec![(two_b.to_owned().unwrap(), three_b_minus_one.to_owned().unwrap())];
+  let mut s = BigNum::from_u32(0).unwrap();
+  let mut i = 1;
+  loop {
+    if i == 1 {
+      // step 2a
+      s = ceil_div(n, &three_b).unwrap();
+      while !conforming(&s) {
+        s = &s + &one;
+      }
+    } else if intervals.len() > 1 {
+      // step 2b
+      s = &s + &one;
+      while !conforming(&s) {
+        s = &s + &one;
+      }
+    } else {
+      // step 2c
+      let (a, upper) = &intervals[0];
+      let mut r = ceil_div(&(&(&(upper * &s) - &two_b) << 1), n).unwrap();
+      'search: loop {
+        let rn = &r * n;
+        let mut candidate = ceil_div(&(&two_b + &rn), upper).unwrap();
+        let limit = ceil_div(&(&three_b + &rn), a).unwrap();
+        while candidate < limit {
+          if conforming(&candidate) {
+            s = candidate;
+            break 'search;
+          }
+          candidate = &candidate + &one;
+        }
+        r = &r + &one;
+      }
+    }
+
+    // step 3
+    let mut narrowed: Vec<(BigNum, BigNum)> = vec![];
+    for (a, upper) in intervals.iter() {
+      let mut r = ceil_div(&(&(&(a * &s) - &three_b) + &one), n).unwrap();
+      let r_max = &(&(upper * &s) - &two_b) / n;
+      while r <= r_max {
+        let rn = &r * n;
+        let new_a = ceil_div(&(&two_b + &rn), &s).unwrap();
+        let new_b = &(&three_b_minus_one + &rn) / &s;
+        let new_a = if new_a > *a { new_a } else { BigNumRef::to_owned(a).unwrap() };
+        let new_b = if new_b < *upper { new_b } else { BigNumRef::to_owned(upper).unwrap() };
+        if new_a <= new_b {
+          narrowed.push((new_a, new_b));
+        }
+        r = &r + &one;
+      }
+    }
+    intervals = merge_intervals(narrowed);
+
+    // step 4
+    if intervals.len() == 1 && intervals[0].0 == intervals[0].1 {
+      let padded = intervals[0].0.to_vec();
+      return rsa::pkcs1_v15_unpad(&padded, k).expect("Recovered plaintext is not PKCS conforming");
+    }
+    i += 1;
+  }
+}
+
+fn merge_intervals(mut intervals: Vec<(BigNum, BigNum)>) -> Vec<(BigNum, BigNum)> {
+  intervals.sort_by(|(a1, _), (a2, _)| a1.cmp(a2));
+  let mut res: Vec<(BigNum, BigNum)> = vec![];
+  for (a, b) in intervals {
+    match res.last_mut() {
+      Some((_, last_b)) if a <= *last_b => {
+        if b > *last_b {
+          *last_b = b;
+        }
+      }
+      _ => res.push((a, b)),
+    }
+  }
+  res
+}
+
+#[allow(dead_code)]
+pub fn compression_attack(
+  known_prefix: &[u8],
+  alphabet: &[u8],
+  terminator: u8,
+  oracle_fn: impl Fn(&[u8]) -> usize,
+) -> Vec<u8> {
+  let mut candidates: Vec<Vec<u8>> = vec![vec![]];
+  let mut extensions = alphabet.to_owned();
+  extensions.push(terminator);
+  loop {
+    let mut scored: Vec<(i64, Vec<u8>)> = candidates
+      .iter()
+      .flat_map(|candidate| {
+        extensions.iter().map(|c| {
+          let mut guess = candidate.clone();
+          guess.push(*c);
+          (0, guess)
+        })
+      })
+      .collect();
+    // ties mean the difference got lost when rounding the compressed length or that the match
+    // crossed into a longer DEFLATE length code, so retry with a shorter part of the known prefix
+    for skip in 0..known_prefix.len().saturating_sub(2) {
+      compression_scores(&known_prefix[skip..], &mut scored, &oracle_fn);
+      let best = scored.iter().map(|(score, _)| *score).min().unwrap();
+      if skip > 0 && scored.iter().filter(|(score, _)| *score == best).count() == 1 {
+        break;
+      }
+    }
+    let best = scored.iter().map(|(score, _)| *score).min().unwrap();
+    let mut tied: Vec<(i64, Vec<u8>)> =
+      scored.into_iter().filter(|(score, _)| *score == best).collect();
+    // the terminator tying with other guesses would end the secret early, so score the tied
+    // guesses again with fresh noise
+    for _ in 0..4 {
+      if tied.len() == 1 || tied.iter().all(|(_, v)| v.last() != Some(&terminator)) {
+        break;
+      }
+      tied.iter_mut().for_each(|(score, _)| *score = 0);
+      compression_scores(known_prefix, &mut tied, &oracle_fn);
+      let best = tied.iter().map(|(score, _)| *score).min().unwrap();
+      tied.retain(|(score, _)| *score == best);
+    }
+    candidates = tied.into_iter().map(|(_, v)| v).collect();
+    if let Some(done) = candidates.iter().find(|v| v.last() == Some(&terminator)) {
+      return done[..done.len() - 1].to_owned();
+    }
+    candidates.truncate(8);
+  }
+}
+
+fn compression_scores(
+  known_prefix: &[u8],
+  scored: &mut [(i64, Vec<u8>)],
+  oracle_fn: &impl Fn(&[u8]) -> usize,
+) {
+  let filler = "{}~!".as_bytes();
+  // symbols that never show up in the request; a small set keeps DEFLATE from falling back to
+  // stored blocks and makes each noise byte cost only a few bits
+  let symbols = b"#$%&*<>?@[]^_`|;";
+  let noise: Vec<u8> = (0..64).map(|_| symbols[rand::random_range(0..symbols.len())]).collect();
+  let with_noise = |noise_len: usize, payload: &[&[u8]]| {
+    let mut input = noise[..noise_len].to_owned();
+    input.extend_from_slice(known_prefix);
+    payload.iter().for_each(|v| input.extend_from_slice(v));
+    oracle_fn(&input) as i64
+  };
+  let mut spills: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+  for (score, guess) in scored.iter_mut() {
+    let (candidate, c) = guess.split_at(guess.len() - 1);
+    // the amounts of noise that push a wrong guess past a block boundary (or a byte boundary
+    // for stream ciphers), a right guess may compress just enough to stay behind it
+    let spills = spills.entry(candidate.to_owned()).or_insert_with(|| {
+      let lengths: Vec<i64> =
+        (0..noise.len()).map(|v| with_noise(v, &[candidate, filler, b"~"])).collect();
+      (2..noise.len() - 1)
+        .filter(|v| lengths[*v] > lengths[*v - 1])
+        .take(4)
+        .flat_map(|v| [v - 1, v, v + 1])
+        .unique()
+        .collect()
+    });
+    // "two tries": the same bytes in both requests, only the right guess extends the match
+    *score += spills
+      .iter()
+      .map(|v| with_noise(*v, &[candidate, c, filler]) - with_noise(*v, &[candidate, filler, c]))
+      .sum::<i64>();
+  }
+}
+
+// single-byte biases of the RC4 keystream, strongest first: z2 is 0 twice as often as it should,
+// z16 leans a lot less towards 0xf0 and z32 towards 0xe0
+#[allow(dead_code)]
+const RC4_BIASES: [(usize, u8); 3] = [(1, 0x00), (15, 0xf0), (31, 0xe0)];
+
+// the strongest bias one of the prefix lengths can line up with the secret byte
+#[allow(dead_code)]
+fn rc4_bias_for(secret_idx: usize) -> usize {
+  RC4_BIASES.iter().position(|(pos, _)| (secret_idx..secret_idx + 16).contains(pos)).unwrap()
+}
+
+// recovers up to 32 bytes of a secret appended to our request, each prefix length lines up one
+// secret byte with each biased keystream position
+#[allow(dead_code)]
+pub fn rc4_bias_attack(
+  secret_len: usize,
+  samples: usize,
+  oracle_fn: impl Fn(&[u8]) -> Vec<u8> + Sync,
+) -> Vec<u8> {
+  assert!(secret_len <= 32, "Only the first 32 keystream bytes have usable biases");
+  let threads = std::thread::available_parallelism().map(|v| v.get()).unwrap_or(1);
+  let mut secret = vec![0u8; secret_len];
+  let useful =
+    |prefix_len: &usize| (0..secret_len).any(|v| RC4_BIASES[rc4_bias_for(v)].0 == v + prefix_len);
+  for prefix_len in (0..16).filter(useful) {
+    let prefix = vec![b'A'; prefix_len];
+    let counts = std::thread::scope(|scope| {
+      let workers: Vec<_> = (0..threads)
+        .map(|worker| {
+          let (prefix, oracle_fn) = (&prefix, &oracle_fn);
+          scope.spawn(move || {
+            let mut counts = [[0u64; 256]; RC4_BIASES.len()];
+            for _ in (worker..samples).step_by(threads) {
+              let ciphertext = oracle_fn(prefix);
+              for (idx, (position, _)) in RC4_BIASES.iter().enumerate() {
+                if let Some(v) = ciphertext.get(*position) {
+                  counts[idx][*v as usize] += 1;
+                }
+              }
+            }
+            counts
+          })
+        })
+        .collect();
+      workers.into_iter().fold([[0u64; 256]; RC4_BIASES.len()], |mut acc, worker| {
+        let counts = worker.join().expect("RC4 bias worker panicked");
+        for (acc, counts) in acc.iter_mut().zip(counts.iter()) {
+          acc.iter_mut().zip(counts.iter()).for_each(|(a, c)| *a += c);
+        }
+        acc
+      })
+    });
+    for (idx, (position, bias)) in RC4_BIASES.iter().enumerate() {
+      let secret_idx = position.checked_sub(prefix_len);
+      let Some(secret_idx) = secret_idx.filter(|v| *v < secret_len && rc4_bias_for(*v) == idx)
+      else {
+        continue;
+      };
+      let (most_common, _) = counts[idx].iter().enumerate().max_by_key(|(_, v)| **v).unwrap();
+      secret[secret_idx] = most_common as u8 ^ bias;
+    }
+  }
+  secret
+}
+
+// with a client chosen IV, any change to the first block of a signed request can be moved into
+// the IV: the first CBC input block, and so the MAC, stays the same. None when the request has no
+// full message block in front of the IV and MAC
+#[allow(dead_code)]
+pub fn cbc_mac_iv_forgery(
+  request: &[u8],
+  forged_message: &[u8],
+  block_size: usize,
+) -> Option<Vec<u8>> {
+  if request.len() < 3 * block_size {
+    return None;
+  }
+  let (message, iv_mac) = request.split_at(request.len() - 2 * block_size);
+  let (iv, mac) = iv_mac.split_at(block_size);
+  assert!(message.len() == forged_message.len(), "The forged message must keep the same length");
+  assert!(message[block_size..] == forged_message[block_size..], "Only the first block can change");
+  let forged_iv: Vec<u8> =
+    (0..block_size).map(|i| iv[i] ^ message[i] ^ forged_message[i]).collect();
+  let mut res = forged_message.to_owned();
+  res.extend(forged_iv);
+  res.extend_from_slice(mac);
+  Some(res)
+}
+
+// with a fixed IV, a captured request (message || MAC) can be continued by one of our own:
+// pad(M) || (M'[..16] ^ MAC) || M'[16..] is signed by the MAC of M'. Gives up when the spliced
+// block would contain a '&' and cut the victim's transaction list short
+#[allow(dead_code)]
+pub fn cbc_mac_length_extension(
+  captured: &[u8],
+  extension: &[u8],
+  block_size: usize,
+) -> Option<Vec<u8>> {
+  let (message, mac) = captured.split_at(captured.len() - block_size);
+  let (extension_message, extension_mac) = extension.split_at(extension.len() - block_size);
+  let spliced: Vec<u8> = (0..block_size).map(|i| extension_message[i] ^ mac[i]).collect();
+  if spliced.contains(&b'&') {
+    return None;
+  }
+  let mut res = cryptog::pkcs7_padding(message.to_owned(), block_size);
+  res.extend(spliced);
+  res.extend_from_slice(&extension_message[block_size..]);
+  res.extend_from_slice(extension_mac);
+  Some(res)
+}
+
+// prefix || "//" and spaces up to a block boundary, its PKCS#7 padding block, then a glue block
+// that brings the CBC state back to zero before the target's own blocks. Everything after the
+// "//" is a comment as long as no line break sneaks into the padding or the glue
+#[allow(dead_code)]
+pub fn cbc_mac_hash_collision(target: &[u8], prefix: &[u8]) -> Vec<u8> {
+  let block_size = 16;
+  assert!(target.len() >= block_size, "The target needs at least one full block");
+  let mut code = prefix.to_owned();
+  code.extend_from_slice(b"//");
+  loop {
+    while !code.len().is_multiple_of(block_size) {
+      code.push(b' ');
+    }
+    let state = cryptog::cbc_mac_hash(&code);
+    let mut res = cryptog::pkcs7_padding(code.clone(), block_size);
+    res.extend(state.iter().zip(target).map(|(a, b)| a ^ b));
+    res.extend_from_slice(&target[block_size..]);
+    if !res[code.len()..res.len() - target.len() + block_size].iter().any(|v| b"\r\n".contains(v)) {
+      return res;
+    }
+    code.push(b' ');
+  }
+}
+
+// the ladder never looks at b, so points on curves sharing a and p but with smooth orders leak the
+// secret modulo each small factor r of their order: Bob's MAC for a point of order r matches the
+// MAC under the key of k * point for exactly one k < r. CRT puts the residues back together
+#[allow(dead_code)]
+pub fn invalid_curve_attack(
+  params: &EcdhParams,
+  invalid_curves: &[(u32, &BigNumRef)],
+  factor_bound: u32,
+  message: &[u8],
+  oracle_fn: impl Fn(&EcPoint) -> Vec<u8>,
+) -> Option<BigNum> {
+  let mut residues: Vec<(BigNum, BigNum)> = vec![];
+  let mut used = vec![];
+  let mut modulus = BigNum::from_u32(1).unwrap();
+  for (b, order) in invalid_curves {
+    let curve = params.curve.with_b(&BigNum::from_u32(*b).unwrap()).unwrap();
+    for r in bignum::small_factors(order, factor_bound).unwrap() {
+      if used.contains(&r) {
+        continue;
+      }
+      let point = curve.point_of_order(order, r).unwrap();
+      let mac = oracle_fn(&point);
+      let mut multiple = EcPoint::Infinity;
+      for k in 0..r {
+        let key = ec::shared_key(&multiple);
+        if cryptog::hmac_sha256(&key, message).unwrap() == mac {
+          residues.push((BigNum::from_u32(k).unwrap(), BigNum::from_u32(r).unwrap()));
+          used.push(r);
+          modulus = &modulus * &BigNum::from_u32(r).unwrap();
+          break;
+        }
+        multiple = curve.add(&multiple, &point).unwrap();
+      }
+      if modulus > params.order {
+        return Some(bignum::crt(&residues).unwrap().0);
+      }
+    }
+  }
+  None
+}
+
+// k in 0..=r/2 such that the MAC under the key of u(kP) matches, u(kP) = u(-kP) so the result is
+// only known up to sign. Consecutive multiples come from differential additions
+fn x_only_brute_force(
+  params: &MontgomeryParams,
+  u: &BigNumRef,
+  r: u32,
+  message: &[u8],
+  mac: &[u8],
+) -> Option<u32> {
+  let curve = &params.curve;
+  let matches = |point: &XzPoint| {
+    let key = ec::shared_key_x_only(&curve.to_u(point).unwrap());
+    cryptog::hmac_sha256(&key, message).unwrap() == mac
+  };
+  let base = curve.to_xz(u).unwrap();
+  let mut previous = XzPoint { u: BigNum::from_u32(1).unwrap(), w: BigNum::new().unwrap() };
+  let mut current = curve.to_xz(u).unwrap();
+  if matches(&previous) {
+    return Some(0);
+  }
+  for k in 1..=r / 2 {
+    if matches(&current) {
+      return Some(k);
+    }
+    let next = if k == 1 {
+      curve.x_double(&current).unwrap()
+    } else {
+      curve.x_add(&current, &base, &previous).unwrap()
+    };
+    previous = std::mem::replace(&mut current, next);
+  }
+  None
+}
+
+// (c, m) with Bob's secret = c or -c mod m, from twist points of small odd prime orders. A point
+// of order m r decides which of the two CRT combinations of +-c and +-k is consistent
+#[allow(dead_code)]
+pub fn twist_residues(
+  params: &MontgomeryParams,
+  factor_bound: u32,
+  message: &[u8],
+  oracle_fn: impl Fn(&BigNumRef) -> Vec<u8>,
+) -> (BigNum, BigNum) {
+  let curve = &params.curve;
+  let twist_order = curve.twist_order(&params.group_order).unwrap();
+  let mut residue = BigNum::new().unwrap();
+  let mut modulus = BigNum::from_u32(1).unwrap();
+  let mut used = vec![];
+  // the order 2 point u = 0 is on the curve as well and tells nothing
+  for r in bignum::small_factors(&twist_order, factor_bound).unwrap().into_iter().filter(|r| *r > 2)
+  {
+    let u = curve.twist_point_of_order(&twist_order, &[r]).unwrap();
+    let Some(k) = x_only_brute_force(params, &u, r, message, &oracle_fn(&u)) else {
+      continue;
+    };
+    let r_bn = BigNum::from_u32(r).unwrap();
+    let combine = |k: u32| {
+      let residues = [
+        (residue.to_owned().unwrap(), modulus.to_owned().unwrap()),
+        (BigNum::from_u32(k).unwrap(), r_bn.to_owned().unwrap()),
+      ];
+      bignum::crt(&residues).unwrap().0
+    };
+    residue = if used.is_empty() {
+      BigNum::from_u32(k).unwrap()
+    } else {
+      used.push(r);
+      let u = curve.twist_point_of_order(&twist_order, &used).unwrap();
+      used.pop();
+      let mac = oracle_fn(&u);
+      let candidate = combine(k);
+      let shared = curve.ladder(&u, &candidate).unwrap();
+      if cryptog::hmac_sha256(&ec::shared_key_x_only(&shared), message).unwrap() == mac {
+        candidate
+      } else {
+        combine(r - k)
+      }
+    };
+    used.push(r);
+    modulus = &modulus * &r_bn;
+  }
+  (residue, modulus)
+}
+
+// the twist leaves Bob's secret modulo m up to sign, kangaroo finds the rest on the curve itself.
+// Since only u(public) is known, the result is the secret or order - secret: both give Bob's
+// shared keys
+#[allow(dead_code)]
+pub fn twist_attack(
+  params: &MontgomeryParams,
+  public: &BigNumRef,
+  factor_bound: u32,
+  message: &[u8],
+  oracle_fn: impl Fn(&BigNumRef) -> Vec<u8>,
+) -> Option<BigNum> {
+  twist_attack_bounded(params, public, factor_bound, &params.order, message, oracle_fn)
+}
+
+// twist_attack for a secret known to be below secret_bound, kangaroo only walks
+// secret_bound / modulus
+#[allow(dead_code)]
+pub fn twist_attack_bounded(
+  params: &MontgomeryParams,
+  public: &BigNumRef,
+  factor_bound: u32,
+  secret_bound: &BigNumRef,
+  message: &[u8],
+  oracle_fn: impl Fn(&BigNumRef) -> Vec<u8>,
+) -> Option<BigNum> {
+  let (residue, modulus) = twist_residues(params, factor_bound, message, oracle_fn);
+  let curve = params.curve.to_weierstrass().unwrap();
+  let base = params.curve.to_weierstrass_point(&params.base).unwrap()?;
+  let public_point = params.curve.to_weierstrass_point(public).unwrap()?;
+  let generator = curve.scalar_mul(&modulus, &base).unwrap();
+  let upper = secret_bound / &modulus;
+  let zero = BigNum::new().unwrap();
+  let negated = &modulus - &residue;
+  for offset in [&residue, &negated] {
+    for point in [public_point.clone(), curve.neg(&public_point).unwrap()] {
+      // point - offset * base = m * (modulus * base)
+      let shift = curve.neg(&curve.scalar_mul(offset, &base).unwrap()).unwrap();
+      let target = curve.add(&point, &shift).unwrap();
+      if let Some(m) = dlog::kangaroo(&curve, &generator, &target, &zero, &upper).unwrap() {
+        let secret = &(&m * &modulus) + offset;
+        if params.curve.ladder(&params.base, &secret).unwrap() == *public {
+          return Some(secret);
+        }
+      }
+    }
+  }
+  None
+}
+
+// elements of order r for the small primes r dividing (p - 1) / q pin the secret modulo r, one
+// brute force against Bob's MAC each, until CRT covers the whole of [0, q)
+#[allow(dead_code)]
+pub fn subgroup_confinement_attack(
+  params: &DhParams,
+  factor_bound: u32,
+  message: &[u8],
+  oracle_fn: impl Fn(&BigNumRef) -> Vec<u8>,
+) -> Option<BigNum> {
+  let group = MultiplicativeGroup { p: params.p.to_owned().unwrap() };
+  let one = BigNum::from_u32(1).unwrap();
+  let p_minus_one = &params.p - &one;
+  let cofactor = &p_minus_one / &params.q;
+  let mut residues = vec![];
+  let mut modulus = BigNum::from_u32(1).unwrap();
+  for r in bignum::small_factors(&cofactor, factor_bound).unwrap() {
+    if params.q.mod_word(r).unwrap() == 0 {
+      continue;
+    }
+    let exponent = &p_minus_one / &BigNum::from_u32(r).unwrap();
+    let h = loop {
+      let h = group.pow(&bignum::random_below(&params.p).unwrap(), &exponent).unwrap();
+      if h.num_bits() != 0 && h != one {
+        break h;
+      }
+    };
+    let mac = oracle_fn(&h);
+    let mut power = group.identity().unwrap();
+    for k in 0..r {
+      if cryptog::hmac_sha256(&group.encode(&power), message).unwrap() == mac {
+        residues.push((BigNum::from_u32(k).unwrap(), BigNum::from_u32(r).unwrap()));
+        modulus = &modulus * &BigNum::from_u32(r).unwrap();
+        break;
+      }
+      power = group.op(&power, &h).unwrap();
+    }
+    if modulus > params.q {
+      return Some(bignum::crt(&residues).unwrap().0);
+    }
+  }
+  None
+}
+
+// DSKS: a key pair of our own making under which `signature` is also valid for `forged`. With
+// p - 1 and q - 1 smooth and the signature generating both groups, e' = log_s(pad(forged)) comes
+// from Pohlig-Hellman modulo p and q. gcd(p - 1, q - 1) = 2, so both logs must share their parity
+#[allow(dead_code)]
+pub fn dsks_attack(
+  message: &[u8],
+  signature: &[u8],
+  key: &RsaPublicKey,
+  forged: &[u8],
+) -> Option<(RsaPublicKey, RsaPrivateKey)> {
+  if !rsa::verify(key, message, signature).unwrap() {
+    return None;
+  }
+  let s = BigNum::from_slice(signature).unwrap();
+  let bits = key.n.num_bits();
+  let one = BigNum::from_u32(1).unwrap();
+  // the padded block depends on the modulus length, which stays the same
+  let target = BigNum::from_slice(&rsa::pkcs1_v15_sign_pad(forged, rsa::modulus_len(&key.n)));
+  let target = target.unwrap();
+  // a prime for which s generates everything and the target has an odd log
+  let suitable_prime = |bits: i32, avoid: &[u32]| loop {
+    let (p, odd_factors) = bignum::smooth_prime(bits, avoid).unwrap();
+    let group = MultiplicativeGroup { p: p.to_owned().unwrap() };
+    let p_minus_one = &p - &one;
+    let s = bignum::nnmod(&s, &p).unwrap();
+    let generates = odd_factors.iter().copied().chain([2]).all(|r| {
+      let exponent = &p_minus_one / &BigNum::from_u32(r).unwrap();
+      group.pow(&s, &exponent).unwrap() != one
+    });
+    let half = &p_minus_one / &BigNum::from_u32(2).unwrap();
+    let non_residue = group.pow(&bignum::nnmod(&target, &p).unwrap(), &half).unwrap() != one;
+    if generates && non_residue {
+      let mut factors: Vec<(u32, u32)> = odd_factors.iter().map(|r| (*r, 1)).collect();
+      factors.push((2, 1));
+      let log = dlog::pohlig_hellman(&group, &s, &bignum::nnmod(&target, &p).unwrap(), &factors);
+      return (p, odd_factors, log.unwrap().unwrap());
+    }
+  };
+  loop {
+    let (p, p_factors, p_log) = suitable_prime(bits / 2, &[]);
+    let (q, _, q_log) = suitable_prime(bits - bits / 2, &p_factors);
+    let n = &p * &q;
+    if n.num_bits() != bits || n <= s || n <= target {
+      continue;
+    }
+    // e = p_log mod p - 1 and e = q_log mod (q - 1) / 2, both logs being odd
+    let q_half = &(&q - &one) / &BigNum::from_u32(2).unwrap();
+    let residues = [(p_log, &p - &one), (bignum::nnmod(&q_log, &q_half).unwrap(), q_half)];
+    let (e, _) = bignum::crt(&residues).unwrap();
+    let phi = &(&p - &one) * &(&q - &one);
+    let Ok(d) = bignum::mod_inverse(&e, &phi) else {
+      continue;
+    };
+    return Some((RsaPublicKey { e, n: n.to_owned().unwrap() }, RsaPrivateKey { d, n }));
+  }
+}
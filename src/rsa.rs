@@ -0,0 +1,129 @@
+use openssl::{
+  bn::{BigNum, BigNumContext, BigNumRef},
+  error::ErrorStack,
+  sha::sha256,
+};
+
+use rand::Rng;
+
+use crate::{
+  bignum::{mod_exp, mod_inverse},
+  cryptog::InvalidPadding,
+};
+
+// DER DigestInfo header for SHA-256, see RFC 8017 section 9.2
+const SHA256_DIGEST_INFO: [u8; 19] = [
+  0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+  0x00, 0x04, 0x20,
+];
+
+#[derive(Debug)]
+pub struct RsaPublicKey {
+  pub e: BigNum,
+  pub n: BigNum,
+}
+
+#[derive(Debug)]
+pub struct RsaPrivateKey {
+  pub d: BigNum,
+  pub n: BigNum,
+}
+
+pub fn generate_keypair(bits: i32, e: u32) -> Result<(RsaPublicKey, RsaPrivateKey), ErrorStack> {
+  let e = BigNum::from_u32(e)?;
+  let one = BigNum::from_u32(1)?;
+  let mut ctx = BigNumContext::new()?;
+  loop {
+    let mut p = BigNum::new()?;
+    let mut q = BigNum::new()?;
+    p.generate_prime(bits / 2, false, None, None)?;
+    q.generate_prime(bits - bits / 2, false, None, None)?;
+    let n = &p * &q;
+    if p == q || n.num_bits() != bits {
+      continue;
+    }
+    let phi = &(&p - &one) * &(&q - &one);
+    let mut gcd = BigNum::new()?;
+    gcd.gcd(&e, &phi, &mut ctx)?;
+    if gcd != one {
+      continue;
+    }
+    let d = mod_inverse(&e, &phi)?;
+    return Ok((RsaPublicKey { e, n: n.to_owned()? }, RsaPrivateKey { d, n }));
+  }
+}
+
+pub fn encrypt(key: &RsaPublicKey, m: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  mod_exp(m, &key.e, &key.n)
+}
+
+pub fn decrypt(key: &RsaPrivateKey, c: &BigNumRef) -> Result<BigNum, ErrorStack> {
+  mod_exp(c, &key.d, &key.n)
+}
+
+pub fn encrypt_bytes(key: &RsaPublicKey, plaintext: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  let m = BigNum::from_slice(plaintext)?;
+  Ok(encrypt(key, &m)?.to_vec())
+}
+
+pub fn decrypt_bytes(key: &RsaPrivateKey, ciphertext: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  let c = BigNum::from_slice(ciphertext)?;
+  Ok(decrypt(key, &c)?.to_vec())
+}
+
+// size of the modulus in bytes, i.e. the "k" of PKCS#1
+pub fn modulus_len(n: &BigNumRef) -> usize {
+  n.num_bytes() as usize
+}
+
+// PKCS#1 v1.5 encryption block: 00 02 <non-zero random bytes> 00 <message>
+pub fn pkcs1_v15_pad(message: &[u8], k: usize) -> Vec<u8> {
+  assert!(message.len() + 11 <= k, "Message too long for PKCS#1 v1.5 padding");
+  let rng = &mut rand::rng();
+  let mut res = vec![0x00, 0x02];
+  res.extend((0..k - message.len() - 3).map(|_| rng.random_range(1..=255u8)));
+  res.push(0x00);
+  res.extend_from_slice(message);
+  res
+}
+
+pub fn pkcs1_v15_unpad(block: &[u8], k: usize) -> Result<Vec<u8>, InvalidPadding> {
+  // big numbers lose their leading zero bytes
+  let mut padded = vec![0; k.saturating_sub(block.len())];
+  padded.extend_from_slice(block);
+  if padded.len() != k || padded[0] != 0x00 || padded[1] != 0x02 {
+    return Err(InvalidPadding {});
+  }
+  match padded.iter().skip(2).position(|v| *v == 0) {
+    Some(idx) if idx >= 8 => Ok(padded[idx + 3..].to_owned()),
+    _ => Err(InvalidPadding {}),
+  }
+}
+
+// PKCS#1 v1.5 signature block: 00 01 ff .. ff 00 DigestInfo || sha256(message)
+pub fn pkcs1_v15_sign_pad(message: &[u8], k: usize) -> Vec<u8> {
+  let mut digest_info = SHA256_DIGEST_INFO.to_vec();
+  digest_info.extend(sha256(message));
+  assert!(digest_info.len() + 11 <= k, "Modulus too short for a PKCS#1 v1.5 signature");
+  let mut res = vec![0x00, 0x01];
+  res.extend(vec![0xff; k - digest_info.len() - 3]);
+  res.push(0x00);
+  res.extend(digest_info);
+  res
+}
+
+pub fn sign(key: &RsaPrivateKey, message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+  let k = modulus_len(&key.n);
+  let block = BigNum::from_slice(&pkcs1_v15_sign_pad(message, k))?;
+  decrypt(key, &block)?.to_vec_padded(k as i32)
+}
+
+// rebuilds the whole expected block instead of parsing the decrypted one
+pub fn verify(key: &RsaPublicKey, message: &[u8], signature: &[u8]) -> Result<bool, ErrorStack> {
+  let s = BigNum::from_slice(signature)?;
+  if s >= key.n {
+    return Ok(false);
+  }
+  let k = modulus_len(&key.n);
+  Ok(encrypt(key, &s)?.to_vec_padded(k as i32)? == pkcs1_v15_sign_pad(message, k))
+}
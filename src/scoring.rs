@@ -0,0 +1,206 @@
+use std::collections::{HashMap, HashSet};
+
+// the tables are embedded so that scoring does not depend on the working directory
+const ENGLISH_DIGRAMS: &str = include_str!("../res/english_digrams.txt");
+const ENGLISH_TRIGRAMS: &str = include_str!("../res/english_trigrams.txt");
+const ENGLISH_WORDS: &str = include_str!("../res/english_words.txt");
+
+// frequency of A..Z in English text, in hundredths of a percent
+const LETTER_WEIGHTS: [u64; 26] = [
+  820, 150, 280, 430, 1270, 220, 200, 610, 700, 16, 77, 400, 240, 670, 750, 190, 12, 600, 630, 910,
+  280, 98, 240, 15, 200, 7,
+];
+// share of letters, spaces, other printable and unprintable bytes in English text
+const LETTER_SHARE: f64 = 0.8;
+const SPACE_SHARE: f64 = 0.18;
+const PRINTABLE_SHARE: f64 = 0.0199;
+const UNPRINTABLE_SHARE: f64 = 0.0001;
+
+// how much a sample looks like English text, higher is better
+pub trait Scorer {
+  fn score(&self, sample: &[u8]) -> f64;
+}
+
+// weighted count of letters, the score used by the first challenges
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LetterFrequency;
+
+impl Scorer for LetterFrequency {
+  fn score(&self, sample: &[u8]) -> f64 {
+    let total: u64 = LETTER_WEIGHTS.iter().sum();
+    let points: u64 = sample
+      .iter()
+      .filter(|c| c.is_ascii_alphabetic())
+      .map(|c| LETTER_WEIGHTS[(c.to_ascii_uppercase() - b'A') as usize])
+      .sum();
+    (points * 10000 / total) as f64
+  }
+}
+
+// negated chi-squared statistic of the byte classes against English, letters counted one by one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChiSquared;
+
+impl Scorer for ChiSquared {
+  fn score(&self, sample: &[u8]) -> f64 {
+    if sample.is_empty() {
+      return 0.0;
+    }
+    // 26 letters, space, other printable, unprintable
+    let mut observed = [0u64; 29];
+    for c in sample {
+      let bucket = match c {
+        c if c.is_ascii_alphabetic() => (c.to_ascii_uppercase() - b'A') as usize,
+        b' ' => 26,
+        c if c.is_ascii_graphic() || matches!(c, b'\t' | b'\n' | b'\r') => 27,
+        _ => 28,
+      };
+      observed[bucket] += 1;
+    }
+    let total: u64 = LETTER_WEIGHTS.iter().sum();
+    let mut expected: Vec<f64> =
+      LETTER_WEIGHTS.iter().map(|w| LETTER_SHARE * *w as f64 / total as f64).collect();
+    expected.extend([SPACE_SHARE, PRINTABLE_SHARE, UNPRINTABLE_SHARE]);
+    let len = sample.len() as f64;
+    -observed
+      .iter()
+      .zip(expected)
+      .map(|(o, e)| (*o as f64 - e * len).powi(2) / (e * len))
+      .sum::<f64>()
+  }
+}
+
+// mean log10 probability of the letter n-grams inside every word. Bytes that can't appear in
+// English prose are charged the floor probability
+#[derive(Debug, Clone)]
+pub struct NgramLogLikelihood {
+  n: usize,
+  log_probs: HashMap<Vec<u8>, f64>,
+  floor: f64,
+}
+
+impl NgramLogLikelihood {
+  // a table of "NGRAM count" lines
+  pub fn from_table(n: usize, table: &str) -> NgramLogLikelihood {
+    let counts = parse_table(table);
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+    let log_probs = counts
+      .into_iter()
+      .filter(|(ngram, _)| ngram.len() == n)
+      .map(|(ngram, count)| (ngram, (count as f64 / total as f64).log10()))
+      .collect();
+    let floor = (0.01 / total as f64).log10();
+    NgramLogLikelihood { n, log_probs, floor }
+  }
+
+  pub fn digrams() -> NgramLogLikelihood {
+    NgramLogLikelihood::from_table(2, ENGLISH_DIGRAMS)
+  }
+
+  pub fn trigrams() -> NgramLogLikelihood {
+    NgramLogLikelihood::from_table(3, ENGLISH_TRIGRAMS)
+  }
+
+  pub fn n(&self) -> usize {
+    self.n
+  }
+
+  pub fn len(&self) -> usize {
+    self.log_probs.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.log_probs.is_empty()
+  }
+}
+
+impl Scorer for NgramLogLikelihood {
+  fn score(&self, sample: &[u8]) -> f64 {
+    let upper = sample.to_ascii_uppercase();
+    let mut sum = 0.0;
+    let mut terms = 0;
+    for word in upper.split(|c| !c.is_ascii_uppercase()) {
+      for window in word.windows(self.n) {
+        sum += self.log_probs.get(window).copied().unwrap_or(self.floor);
+        terms += 1;
+      }
+    }
+    let unprintable = sample
+      .iter()
+      .filter(|c| !c.is_ascii_graphic() && !matches!(c, b' ' | b'\t' | b'\n' | b'\r'))
+      .count();
+    sum += unprintable as f64 * self.floor;
+    terms += unprintable;
+    if terms == 0 { self.floor } else { sum / terms as f64 }
+  }
+}
+
+// share of the sample covered by dictionary words
+#[derive(Debug, Clone)]
+pub struct WordDictionary {
+  words: HashSet<Vec<u8>>,
+}
+
+impl WordDictionary {
+  // one word per line, case insensitive
+  pub fn from_list(list: &str) -> WordDictionary {
+    let words = list
+      .lines()
+      .map(|v| v.trim().to_ascii_uppercase().into_bytes())
+      .filter(|v| !v.is_empty())
+      .collect();
+    WordDictionary { words }
+  }
+
+  pub fn english() -> WordDictionary {
+    WordDictionary::from_list(ENGLISH_WORDS)
+  }
+
+  pub fn contains(&self, word: &[u8]) -> bool {
+    self.words.contains(&word.to_ascii_uppercase())
+  }
+
+  // every word uppercase, sorted
+  pub fn words(&self) -> Vec<&[u8]> {
+    let mut res: Vec<&[u8]> = self.words.iter().map(|v| v.as_slice()).collect();
+    res.sort();
+    res
+  }
+
+  pub fn len(&self) -> usize {
+    self.words.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.is_empty()
+  }
+}
+
+impl Scorer for WordDictionary {
+  fn score(&self, sample: &[u8]) -> f64 {
+    if sample.is_empty() {
+      return 0.0;
+    }
+    // apostrophes are dropped so that "don't" is looked up as DONT
+    let covered: usize = sample
+      .split(|c| !c.is_ascii_alphanumeric() && *c != b'\'')
+      .filter(|word| {
+        let word: Vec<u8> = word.iter().filter(|c| **c != b'\'').copied().collect();
+        !word.is_empty() && self.contains(&word)
+      })
+      .map(|word| word.len())
+      .sum();
+    covered as f64 / sample.len() as f64
+  }
+}
+
+fn parse_table(table: &str) -> Vec<(Vec<u8>, u64)> {
+  table
+    .lines()
+    .filter_map(|v| v.split_once(' '))
+    .map(|(ngram, count)| {
+      let count = count.trim().parse::<u64>().expect("Invalid count in frequency table");
+      (ngram.as_bytes().to_owned(), count)
+    })
+    .collect()
+}
@@ -21,6 +21,7 @@ pub fn xor_fixed_length(a :&[u8], b :&[u8]) -> Result<Vec<u8>,XORMismatchSizeErr
 }
 
 
+#[allow(dead_code)]
 pub fn xor_single_byte(a :&[u8], b :u8) -> Vec<u8>{
   a.iter().map(|c| c ^ b).collect()
 }
@@ -0,0 +1,290 @@
+use std::ops::RangeInclusive;
+
+use crate::{distance::hamming_distance, scoring::Scorer, xor};
+
+// a set of bytes a plaintext may contain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Charset {
+  allowed: [bool; 256],
+}
+
+impl Charset {
+  pub fn from_bytes(bytes: &[u8]) -> Charset {
+    let mut allowed = [false; 256];
+    bytes.iter().for_each(|c| allowed[*c as usize] = true);
+    Charset { allowed }
+  }
+
+  pub fn any() -> Charset {
+    Charset { allowed: [true; 256] }
+  }
+
+  // ASCII graphic characters and whitespace
+  pub fn printable() -> Charset {
+    let mut res = Charset::from_bytes(b" \t\n\r");
+    (0..=255u8).filter(|c| c.is_ascii_graphic()).for_each(|c| res.allowed[c as usize] = true);
+    res
+  }
+
+  // letters, digits, space and the punctuation of ordinary prose, with the / that splits the
+  // verses of lyrics
+  pub fn prose() -> Charset {
+    let mut res = Charset::from_bytes(b" \n\r.,;:!?'\"-()/");
+    (0..=255u8).filter(|c| c.is_ascii_alphanumeric()).for_each(|c| res.allowed[c as usize] = true);
+    res
+  }
+
+  pub fn contains(&self, c: u8) -> bool {
+    self.allowed[c as usize]
+  }
+
+  pub fn accepts(&self, text: &[u8]) -> bool {
+    text.iter().all(|c| self.contains(*c))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+  pub key: u8,
+  pub score: f64,
+  pub plaintext: Vec<u8>,
+}
+
+// every key, best first
+pub fn crack_single_byte_xor(ciphertext: &[u8], scorer: &dyn Scorer) -> Vec<Candidate> {
+  crack_single_byte_xor_with(ciphertext, scorer, &Charset::any())
+}
+
+// every key whose plaintext fits in charset, best first
+pub fn crack_single_byte_xor_with(
+  ciphertext: &[u8],
+  scorer: &dyn Scorer,
+  charset: &Charset,
+) -> Vec<Candidate> {
+  let mut res: Vec<Candidate> = (0..=255u8)
+    .map(|key| (key, xor::xor_single_byte(ciphertext, key)))
+    .filter(|(_, plaintext)| charset.accepts(plaintext))
+    .map(|(key, plaintext)| Candidate { key, score: scorer.score(&plaintext), plaintext })
+    .collect();
+  res.sort_by(|a, b| b.score.total_cmp(&a.score));
+  res
+}
+
+// the lines that decrypt under some single byte key as (line index, best candidate), the most
+// likely line first. Lines without any candidate are left out
+pub fn detect_single_byte_xor<T: AsRef<[u8]>>(
+  lines: &[T],
+  scorer: &dyn Scorer,
+  charset: &Charset,
+) -> Vec<(usize, Candidate)> {
+  let mut res: Vec<(usize, Candidate)> = lines
+    .iter()
+    .enumerate()
+    .filter_map(|(idx, line)| {
+      crack_single_byte_xor_with(line.as_ref(), scorer, charset)
+        .into_iter()
+        .next()
+        .map(|v| (idx, v))
+    })
+    .collect();
+  res.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+  res
+}
+
+// how to rank the candidate key sizes of a repeating-key XOR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeysizeEstimator {
+  // mean Hamming distance between consecutive blocks, per byte
+  Hamming,
+  // mean index of coincidence of the columns
+  IndexOfCoincidence,
+  // share of bytes equal to the byte keysize positions later
+  Autocorrelation,
+}
+
+impl KeysizeEstimator {
+  // the sizes that fit at least twice in the ciphertext as (keysize, score), most likely first
+  pub fn rank(&self, ciphertext: &[u8], keysizes: RangeInclusive<usize>) -> Vec<(usize, f64)> {
+    let mut res: Vec<(usize, f64)> = keysizes
+      .filter(|ks| *ks > 0 && 2 * ks <= ciphertext.len())
+      .map(|ks| (ks, self.score(ciphertext, ks)))
+      .collect();
+    res.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    res
+  }
+
+  fn score(&self, ciphertext: &[u8], keysize: usize) -> f64 {
+    match self {
+      KeysizeEstimator::Hamming => {
+        let blocks: Vec<&[u8]> = ciphertext.chunks_exact(keysize).take(30).collect();
+        let pairs = blocks.len() / 2;
+        let distance: u32 = blocks
+          .chunks_exact(2)
+          .map(|v| hamming_distance(v[0], v[1]).expect("Blocks have the same size"))
+          .sum();
+        -(distance as f64) / (pairs * keysize) as f64
+      }
+      KeysizeEstimator::IndexOfCoincidence => {
+        let columns = transpose(ciphertext, keysize);
+        columns.iter().map(|v| index_of_coincidence(v)).sum::<f64>() / keysize as f64
+      }
+      KeysizeEstimator::Autocorrelation => {
+        let shifted = ciphertext.iter().skip(keysize);
+        let matches = ciphertext.iter().zip(shifted).filter(|(a, b)| a == b).count();
+        matches as f64 / (ciphertext.len() - keysize) as f64
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatingXorSolution {
+  pub keysize: usize,
+  pub key: Vec<u8>,
+  pub plaintext: Vec<u8>,
+  pub score: f64,
+}
+
+// probability that two bytes picked from the sample are equal
+pub fn index_of_coincidence(sample: &[u8]) -> f64 {
+  if sample.len() < 2 {
+    return 0.0;
+  }
+  let mut counts = [0u64; 256];
+  sample.iter().for_each(|c| counts[*c as usize] += 1);
+  let n = sample.len() as u64;
+  counts.iter().map(|c| c * c.saturating_sub(1)).sum::<u64>() as f64 / (n * (n - 1)) as f64
+}
+
+// byte i goes to column i % keysize
+pub fn transpose(ciphertext: &[u8], keysize: usize) -> Vec<Vec<u8>> {
+  let mut columns = vec![vec![]; keysize];
+  ciphertext.iter().enumerate().for_each(|(idx, c)| columns[idx % keysize].push(*c));
+  columns
+}
+
+// the 5 most likely key sizes up to 40, every one broken column by column, best solution first
+pub fn break_repeating_xor(
+  ciphertext: &[u8],
+  scorer: &dyn Scorer,
+  estimator: KeysizeEstimator,
+) -> Vec<RepeatingXorSolution> {
+  break_repeating_xor_with(ciphertext, scorer, estimator, 2..=40, 5, &Charset::any())
+}
+
+// columns without any candidate in charset make their key size fail
+pub fn break_repeating_xor_with(
+  ciphertext: &[u8],
+  scorer: &dyn Scorer,
+  estimator: KeysizeEstimator,
+  keysizes: RangeInclusive<usize>,
+  tries: usize,
+  charset: &Charset,
+) -> Vec<RepeatingXorSolution> {
+  let mut res: Vec<RepeatingXorSolution> = estimator
+    .rank(ciphertext, keysizes)
+    .into_iter()
+    .take(tries)
+    .filter_map(|(keysize, _)| {
+      let key: Option<Vec<u8>> = transpose(ciphertext, keysize)
+        .iter()
+        .map(|column| crack_single_byte_xor_with(column, scorer, charset).first().map(|v| v.key))
+        .collect();
+      let key = key?;
+      let plaintext = xor::xor_repeating_key(&key, ciphertext);
+      let score = scorer.score(&plaintext);
+      Some(RepeatingXorSolution { keysize, key, plaintext, score })
+    })
+    .collect();
+  res.sort_by(|a, b| b.score.total_cmp(&a.score));
+  res
+}
+
+// decrypted bytes before the current column that are scored along with it
+const CTR_CONTEXT: usize = 3;
+
+// a recovered keystream, every byte with how sure the guess is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keystream {
+  pub bytes: Vec<u8>,
+  // score margin of the byte over the runner-up, infinite when it was the only candidate or set
+  // by hand
+  pub confidence: Vec<f64>,
+  // number of ciphertexts long enough to reach the byte
+  pub coverage: Vec<usize>,
+}
+
+impl Keystream {
+  pub fn len(&self) -> usize {
+    self.bytes.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.bytes.is_empty()
+  }
+
+  // manual override of a single byte, nothing happens past the end of the keystream
+  pub fn set(&mut self, idx: usize, byte: u8) {
+    if idx < self.len() {
+      self.bytes[idx] = byte;
+      self.confidence[idx] = f64::INFINITY;
+    }
+  }
+
+  // override the bytes under a known plaintext found at offset in ciphertext, the plaintext past
+  // the end of the ciphertext or of the keystream is ignored
+  pub fn set_plaintext(&mut self, ciphertext: &[u8], offset: usize, plaintext: &[u8]) {
+    let ciphertext = ciphertext.get(offset..).unwrap_or_default();
+    for (idx, (c, p)) in ciphertext.iter().zip(plaintext).enumerate() {
+      self.set(offset + idx, c ^ p);
+    }
+  }
+
+  // the indices guessed with a confidence below threshold
+  pub fn uncertain(&self, threshold: f64) -> Vec<usize> {
+    (0..self.len()).filter(|idx| self.confidence[*idx] < threshold).collect()
+  }
+
+  // ciphertext bytes past the end of the keystream are dropped
+  pub fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8> {
+    ciphertext.iter().zip(&self.bytes).map(|(c, k)| c ^ k).collect()
+  }
+}
+
+// recover the keystream shared by ciphertexts of any length encrypted under the same nonce. Every
+// column is guessed from the ciphertexts that reach it, scoring the new plaintext bytes together
+// with the few already decrypted before them
+pub fn break_fixed_nonce_ctr<T: AsRef<[u8]>>(ciphertexts: &[T], scorer: &dyn Scorer) -> Keystream {
+  let len = ciphertexts.iter().map(|v| v.as_ref().len()).max().unwrap_or_default();
+  let mut res = Keystream { bytes: vec![], confidence: vec![], coverage: vec![] };
+  let printable = Charset::printable();
+  for idx in 0..len {
+    let reaching: Vec<&[u8]> =
+      ciphertexts.iter().map(|v| v.as_ref()).filter(|v| v.len() > idx).collect();
+    let column: Vec<u8> = reaching.iter().map(|v| v[idx]).collect();
+    // fall back to every key when no printable plaintext exists
+    let mut keys: Vec<u8> =
+      (0..=255u8).filter(|k| printable.accepts(&xor::xor_single_byte(&column, *k))).collect();
+    if keys.is_empty() {
+      keys = (0..=255).collect();
+    }
+    let start = idx.saturating_sub(CTR_CONTEXT);
+    let mut scores: Vec<(f64, u8)> = keys
+      .into_iter()
+      .map(|k| {
+        let mut sample = vec![];
+        for ciphertext in &reaching {
+          sample.extend(ciphertext[start..idx].iter().zip(&res.bytes[start..]).map(|(c, s)| c ^ s));
+          sample.push(ciphertext[idx] ^ k);
+          sample.push(b'\n');
+        }
+        (scorer.score(&sample), k)
+      })
+      .collect();
+    scores.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    res.bytes.push(scores[0].1);
+    res.confidence.push(scores.get(1).map_or(f64::INFINITY, |(second, _)| scores[0].0 - second));
+    res.coverage.push(reaching.len());
+  }
+  res
+}
@@ -0,0 +1,124 @@
+use std::io::{Read, Write};
+
+use cryptopals_challeges_rs::htb64::base64::{
+  Base64Reader, Base64Writer, Config, MIME, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD,
+};
+
+#[test]
+fn rfc_vectors() {
+  // RFC 4648 section 10, plus the symbols that differ in the URL safe alphabet
+  let vectors: [(&[u8], &str, &str); 9] = [
+    (b"", "", ""),
+    (b"f", "Zg==", "Zg=="),
+    (b"fo", "Zm8=", "Zm8="),
+    (b"foo", "Zm9v", "Zm9v"),
+    (b"foob", "Zm9vYg==", "Zm9vYg=="),
+    (b"fooba", "Zm9vYmE=", "Zm9vYmE="),
+    (b"foobar", "Zm9vYmFy", "Zm9vYmFy"),
+    (&[0xfb, 0xff, 0xbf], "+/+/", "-_-_"),
+    (&[0xfb, 0xff], "+/8=", "-_8="),
+  ];
+  for (bytes, standard, url_safe) in vectors {
+    let configs = [
+      (STANDARD, standard),
+      (STANDARD_NO_PAD, standard.trim_end_matches('=')),
+      (URL_SAFE, url_safe),
+      (URL_SAFE_NO_PAD, url_safe.trim_end_matches('=')),
+    ];
+    for (config, text) in configs {
+      assert!(config.encode(bytes) == text);
+      assert!(config.decode(text).unwrap() == bytes);
+      assert!(config.decode_lenient(text).unwrap() == bytes);
+    }
+  }
+}
+
+#[test]
+fn round_trip() {
+  for len in 0..64 {
+    let bytes: Vec<u8> = rand::random_iter().take(len).collect();
+    for config in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD, MIME] {
+      let text = config.encode(&bytes);
+      assert!(config.decode(&text).unwrap() == bytes);
+      assert!(config.decode_lenient(&text).unwrap() == bytes);
+    }
+  }
+}
+
+#[test]
+fn mime_wrapping() {
+  let bytes = vec![0xfb; 120];
+  let text = MIME.encode(&bytes);
+  let lines: Vec<&str> = text.split("\r\n").collect();
+  assert!(lines.len() == 3);
+  assert!(lines[0].len() == 76 && lines[1].len() == 76 && lines[2].len() == 8);
+  assert!(lines.concat() == STANDARD.encode(&bytes));
+  assert!(MIME.decode(text.as_bytes()).unwrap() == bytes);
+  assert!(STANDARD.decode(text.as_bytes()).is_err());
+  // a zero line length doesn't wrap
+  let unwrapped = Config { wrap: Some(0), ..MIME };
+  assert!(unwrapped.encode(&bytes) == STANDARD.encode(&bytes));
+  assert!(unwrapped.decode(text.as_bytes()).is_err());
+}
+
+#[test]
+fn mime_misplaced_line_breaks() {
+  assert!(MIME.decode("SGVs").unwrap() == b"Hel");
+  assert!(MIME.decode("SG\nVs").unwrap_err().position() == 2);
+  assert!(MIME.decode("SG\r\nVs").unwrap_err().position() == 2);
+  assert!(MIME.decode_lenient("SG\r\nVs").unwrap() == b"Hel");
+  let bytes = vec![0xfb; 120];
+  let text = MIME.encode(&bytes);
+  // a bare \n, a missing break and a dangling \r after a full line
+  assert!(MIME.decode(text.replace("\r\n", "\n")).unwrap_err().position() == 76);
+  assert!(MIME.decode(text.replacen("\r\n", "", 1)).unwrap_err().position() == 76);
+  assert!(MIME.decode(&text[..77]).unwrap_err().position() == 76);
+  assert!(MIME.decode(&text[..78]).unwrap() == bytes[..57]);
+}
+
+#[test]
+fn strict_errors() {
+  let err = |config: Config, text: &str| config.decode(text.as_bytes()).unwrap_err();
+  assert!(
+    err(STANDARD, "SGVs*G8=").to_string() == "Invalid char for base64 string '*' at position 4"
+  );
+  assert!(err(STANDARD, "SGVsbG8").position() == 7);
+  assert!(err(STANDARD, "SGVsbG8==").position() == 8);
+  assert!(err(STANDARD, "SG=sbG8=").position() == 3);
+  assert!(err(STANDARD_NO_PAD, "SGVsbG8=").position() == 7);
+  assert!(
+    err(STANDARD, "SGVsb").to_string() == "Truncated base64 string, lone symbol at position 4"
+  );
+  // 'p' leaves a set bit after the last byte
+  assert!(err(STANDARD, "SGVsbGp=").position() == 6);
+  assert!(err(URL_SAFE, "ab+/").position() == 2);
+}
+
+#[test]
+fn lenient_decoding() {
+  let expected = b"Hello?>".to_vec();
+  for text in ["SGVsbG8/Pg==", "SGVsbG8_Pg", " SGVs\nbG8/\tPg= = ", "SGVsbG8/Ph=="] {
+    assert!(STANDARD.decode_lenient(text.as_bytes()).unwrap() == expected);
+  }
+  assert!(STANDARD.decode_lenient(b"SGVs*G8=").unwrap_err().position() == 4);
+  assert!(STANDARD.decode_lenient(b"SG==SG==").is_err());
+}
+
+#[test]
+fn streaming_round_trip() {
+  let bytes: Vec<u8> = (0..5000).map(|v| (v * 7 % 256) as u8).collect();
+  let mut writer = Base64Writer::new(vec![], MIME);
+  for chunk in bytes.chunks(37) {
+    writer.write_all(chunk).unwrap();
+  }
+  let text = writer.finish().unwrap();
+  assert!(text == MIME.encode(&bytes).into_bytes());
+  let mut res = vec![];
+  Base64Reader::new(&text[..], MIME).read_to_end(&mut res).unwrap();
+  assert!(res == bytes);
+
+  let mut res = vec![];
+  Base64Reader::lenient(&b"SGVs bG8\n"[..], STANDARD).read_to_end(&mut res).unwrap();
+  assert!(res == b"Hello");
+  assert!(Base64Reader::new(&b"SGVsbG8"[..], STANDARD).read_to_end(&mut vec![]).is_err());
+}
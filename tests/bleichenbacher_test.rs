@@ -0,0 +1,43 @@
+use cryptopals_challeges_rs::{oracle, oracle_hacker, rsa};
+use openssl::bn::BigNum;
+
+fn run_attack(bits: i32) {
+  let message = "kick it, CC".as_bytes();
+  let (public_key, private_key) = rsa::generate_keypair(bits, 3).unwrap();
+  let k = rsa::modulus_len(&public_key.n);
+  let padded = rsa::pkcs1_v15_pad(message, k);
+  let ciphertext = rsa::encrypt(&public_key, &BigNum::from_slice(&padded).unwrap()).unwrap();
+  assert!(oracle::oracle_rsa_pkcs1_padding_validator(&ciphertext, &private_key));
+
+  let res = oracle_hacker::bleichenbacher_attack(&ciphertext, &public_key, |c| {
+    oracle::oracle_rsa_pkcs1_padding_validator(c, &private_key)
+  });
+  assert!(res == message);
+}
+
+#[test]
+fn pkcs1_padding_round_trip() {
+  let padded = rsa::pkcs1_v15_pad("I AM HERE".as_bytes(), 32);
+  assert!(padded.len() == 32);
+  assert!(padded[0] == 0 && padded[1] == 2);
+  assert!(rsa::pkcs1_v15_unpad(&padded, 32).unwrap() == "I AM HERE".as_bytes());
+  assert!(rsa::pkcs1_v15_unpad(&padded[1..], 32).unwrap() == "I AM HERE".as_bytes());
+  assert!(rsa::pkcs1_v15_unpad(&[0x00, 0x01, 0xff, 0x00, 0x41], 5).is_err());
+}
+
+#[test]
+fn challenge_47() {
+  run_attack(256);
+}
+
+// challenge 48 with a modulus small enough for the default run
+#[test]
+fn bleichenbacher_384() {
+  run_attack(384);
+}
+
+#[test]
+#[ignore = "the full 768 bit attack takes over a minute, run with --ignored"]
+fn challenge_48() {
+  run_attack(768);
+}
@@ -0,0 +1,16 @@
+use cryptopals_challeges_rs::{cryptog, htb64::hex, oracle_hacker};
+
+#[test]
+fn challenge_50() {
+  let target = b"alert('MZA who was that?');\n";
+  assert!(hex::encode(cryptog::cbc_mac_hash(target)) == "296b8d7cb78a243dda4d0a61d33bbdd1");
+  let forged = oracle_hacker::cbc_mac_hash_collision(target, b"alert('Ayo, the Wu is back!');");
+  assert!(forged.starts_with(b"alert('Ayo, the Wu is back!');//"));
+  assert!(forged.ends_with(b"\n") && forged.iter().filter(|v| **v == b'\n').count() == 1);
+  assert!(cryptog::cbc_mac_hash(&forged) == cryptog::cbc_mac_hash(target));
+  // the forged snippet survives a round trip through CBC with its own padding intact
+  let key = b"YELLOW SUBMARINE";
+  let ciphertext = cryptog::aes_cbc_encrypt(&[0; 16], key, &forged).unwrap();
+  let decrypted = cryptog::aes_cbc_decrypt_no_unpadding(&[0; 16], key, &ciphertext).unwrap();
+  assert!(cryptog::validate_undo_pkcs7_padding(&decrypted) == Ok(forged));
+}
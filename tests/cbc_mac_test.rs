@@ -0,0 +1,59 @@
+use cryptopals_challeges_rs::{
+  cryptog,
+  oracle::{self, Transfer},
+  oracle_hacker,
+};
+
+#[test]
+fn cbc_mac_chaining() {
+  let key = b"YELLOW SUBMARINE";
+  let iv = [7u8; 16];
+  let message = b"from=1&to=2&amount=3 and then some more";
+  let ciphertext = cryptog::aes_cbc_encrypt(&iv, key, message).unwrap();
+  assert!(cryptog::cbc_mac(&iv, key, message).unwrap() == ciphertext[ciphertext.len() - 16..]);
+  assert!(
+    cryptog::cbc_mac_zero_iv(key, message).unwrap()
+      == cryptog::cbc_mac(&[0; 16], key, message).unwrap()
+  );
+}
+
+#[test]
+fn challenge_49_iv_forgery() {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let (victim, attacker) = (1, 2);
+  let request = oracle::oracle_transfer_client(attacker, attacker, 1000000, &key);
+  assert!(
+    oracle::oracle_transfer_server(&request, &key)
+      == Some(Transfer { from: attacker, to: attacker, amount: 1000000 })
+  );
+  let forged_message = format!("from={victim}&to={attacker}&amount=1000000").into_bytes();
+  let mut tampered = request.clone();
+  tampered[..forged_message.len()].copy_from_slice(&forged_message);
+  assert!(oracle::oracle_transfer_server(&tampered, &key).is_none());
+  let forged = oracle_hacker::cbc_mac_iv_forgery(&request, &forged_message, 16).unwrap();
+  assert!(
+    oracle::oracle_transfer_server(&forged, &key)
+      == Some(Transfer { from: victim, to: attacker, amount: 1000000 })
+  );
+  // an IV, a MAC and less than one block of message
+  assert!(oracle_hacker::cbc_mac_iv_forgery(&[0; 40], &[1; 8], 16).is_none());
+}
+
+#[test]
+fn challenge_49_length_extension() {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let (victim, attacker) = (1, 2);
+  let extension =
+    oracle::oracle_transfer_client_multi(attacker, &[(attacker, 1), (attacker, 1000000)], &key);
+  // keep sniffing the victim's transfers until one splices cleanly
+  let forged = (0..)
+    .find_map(|v| {
+      let captured = oracle::oracle_transfer_client_multi(victim, &[(3, 500 + v), (4, 1000)], &key);
+      assert!(oracle::oracle_transfer_server_multi(&captured, &key).unwrap().len() == 2);
+      oracle_hacker::cbc_mac_length_extension(&captured, &extension, 16)
+    })
+    .unwrap();
+  let transfers = oracle::oracle_transfer_server_multi(&forged, &key).unwrap();
+  assert!(transfers.iter().all(|v| v.from == victim));
+  assert!(transfers.contains(&Transfer { from: victim, to: attacker, amount: 1000000 }));
+}
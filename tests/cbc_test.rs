@@ -1,4 +1,4 @@
-use cryptopals_challeges_rs::{cryptog::{aes_cbc_decrypt, aes_cbc_encrypt}, oracle};
+use cryptopals_challeges_rs::{cryptog::{aes_cbc_decrypt, aes_cbc_encrypt}, htb64::hex, oracle};
 
 
 #[test]
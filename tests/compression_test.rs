@@ -0,0 +1,75 @@
+use std::io::Write;
+
+use cryptopals_challeges_rs::{cryptog, oracle, oracle_hacker};
+use flate2::{Compression, write::DeflateEncoder};
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+const SHORT_SESSION_ID: &str = "S2lsbCBtZQ==";
+
+// the request of the challenge oracles with a shorter session id, so that the default run is quick
+fn short_session_oracle(cbc: bool) -> impl Fn(&[u8]) -> usize {
+  move |plaintext| {
+    let request = format!(
+      "POST / HTTP/1.1\r\nHost: hapless.com\r\nCookie: sessionid={}\r\nContent-Length: {}\r\n",
+      SHORT_SESSION_ID,
+      plaintext.len()
+    );
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(request.as_bytes()).unwrap();
+    encoder.write_all(plaintext).unwrap();
+    let compressed = encoder.finish().unwrap();
+    let key: Vec<u8> = rand::random_iter().take(16).collect();
+    if cbc {
+      let iv: Vec<u8> = rand::random_iter().take(16).collect();
+      cryptog::aes_cbc_encrypt(&iv, &key, &compressed).unwrap().len()
+    } else {
+      cryptog::aes_ctr(rand::random(), &key, &compressed).len()
+    }
+  }
+}
+
+#[test]
+fn short_session_ctr() {
+  let res = oracle_hacker::compression_attack(
+    b"sessionid=",
+    BASE64_ALPHABET,
+    b'\r',
+    short_session_oracle(false),
+  );
+  assert!(res == SHORT_SESSION_ID.as_bytes());
+}
+
+#[test]
+fn short_session_cbc() {
+  let res = oracle_hacker::compression_attack(
+    b"sessionid=",
+    BASE64_ALPHABET,
+    b'\r',
+    short_session_oracle(true),
+  );
+  assert!(res == SHORT_SESSION_ID.as_bytes());
+}
+
+#[test]
+#[ignore = "recovers the full 44 character session id, run with --ignored"]
+fn challenge_51_ctr() {
+  let res = oracle_hacker::compression_attack(
+    b"sessionid=",
+    BASE64_ALPHABET,
+    b'\r',
+    oracle::oracle_compression_ctr,
+  );
+  assert!(res == b"TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=");
+}
+
+#[test]
+#[ignore = "recovers the full 44 character session id, run with --ignored"]
+fn challenge_51_cbc() {
+  let res = oracle_hacker::compression_attack(
+    b"sessionid=",
+    BASE64_ALPHABET,
+    b'\r',
+    oracle::oracle_compression_cbc,
+  );
+  assert!(res == b"TmV2ZXIgcmV2ZWFsIHRoZSBXdS1UYW5nIFNlY3JldCE=");
+}
@@ -18,5 +18,5 @@ fn challenge_13() {
   assert!(obj.get("foo").unwrap() == &CookieValue::StringValue("bar".to_owned()));
   assert!(obj.get("baz").unwrap() == &CookieValue::StringValue("qux".to_owned()));
   assert!(obj.get("zap").unwrap() == &CookieValue::StringValue("zazzle".to_owned()));
-  assert!(profile_for("foo@bar.com".to_owned()) == "email=foo@bar.com&uid=10&role=user".to_owned());
+  assert!(profile_for("foo@bar.com".to_owned()) == "email=foo@bar.com&uid=10&role=user");
 }
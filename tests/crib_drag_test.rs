@@ -0,0 +1,177 @@
+use cryptopals_challeges_rs::{
+  crib_drag::{self, CribError, CribSession, auto_crib_drag, crib_placements, escape},
+  cryptog,
+  htb64::base64,
+  scoring::{NgramLogLikelihood, WordDictionary},
+  xor_attack::Charset,
+};
+
+const PLAINTEXTS: [&[u8]; 4] = [
+  b"the quick brown fox jumps over the lazy dog",
+  b"we attack the castle at dawn",
+  b"meet me by the old oak tree tonight",
+  b"\x00\xffbinary\n",
+];
+
+fn session() -> (CribSession, Vec<u8>) {
+  let keystream: Vec<u8> = rand::random_iter().take(64).collect();
+  let ciphertexts =
+    PLAINTEXTS.iter().map(|p| p.iter().zip(&keystream).map(|(p, k)| p ^ k).collect()).collect();
+  (CribSession::new(ciphertexts), keystream)
+}
+
+#[test]
+fn guess_and_undo() {
+  let (mut session, keystream) = session();
+  assert!(session.keystream().len() == PLAINTEXTS[0].len());
+  assert!(session.decrypt(0).unwrap().iter().all(|v| v.is_none()));
+  session.guess(1, 3, b"attack").unwrap();
+  assert!(session.keystream()[3..9].iter().zip(&keystream[3..9]).all(|(a, b)| *a == Some(*b)));
+  assert!(session.decrypt(0).unwrap()[3..9] == b" quick".map(Some));
+  session.set(0, keystream[0]).unwrap();
+  session.revert(4..6).unwrap();
+  assert!(session.keystream()[4..6] == [None, None]);
+  session.undo().unwrap();
+  session.undo().unwrap();
+  assert!(session.keystream()[0].is_none() && session.keystream()[4].is_some());
+  session.undo().unwrap();
+  assert!(session.keystream().iter().all(|v| v.is_none()));
+  assert!(session.undo() == Err(CribError::NothingToUndo));
+  assert!(session.guess(9, 0, b"x") == Err(CribError::LineOutOfRange(9)));
+  assert!(session.set(100, 0) == Err(CribError::ColumnOutOfRange(100)));
+  // the guess runs past the end of the line
+  let len = session.ciphertexts()[1].len();
+  assert!(session.guess(1, len - 2, b"abc") == Err(CribError::ColumnOutOfRange(len)));
+  assert!(session.keystream().iter().all(|v| v.is_none()));
+}
+
+#[test]
+fn drag() {
+  let (mut session, keystream) = session();
+  let trigrams = NgramLogLikelihood::trigrams();
+  let matches = session.drag(b" the ", &trigrams).to_vec();
+  assert!(!matches.is_empty());
+  assert!(matches.windows(2).all(|v| v[0].score >= v[1].score));
+  let idx = matches.iter().position(|v| v.line == 1 && v.offset == 9).unwrap();
+  assert!(matches[idx].keystream == keystream[9..14]);
+  assert!(matches[idx].fragments.iter().any(|(line, v)| *line == 0 && v == b" brow"));
+  session.accept(idx).unwrap();
+  assert!(session.decrypt(2).unwrap()[9..14] == b"y the".map(Some));
+  assert!(session.accept(matches.len()) == Err(CribError::NoSuchMatch(matches.len())));
+  assert!(session.drag(b"", &trigrams).is_empty());
+}
+
+#[test]
+fn escaping() {
+  assert!(
+    escape(&[Some(b'a'), None, Some(b'\\'), Some(0), Some(0xff), Some(b' ')])
+      == "a·\\\\\\x00\\xff "
+  );
+  let (mut session, keystream) = session();
+  session.guess(3, 0, PLAINTEXTS[3]).unwrap();
+  assert!(session.keystream()[..9].iter().zip(&keystream).all(|(a, b)| *a == Some(*b)));
+  assert!(session.render().contains("03 | \\x00\\xffbinary\\x0a"));
+}
+
+#[test]
+fn save_and_load() {
+  let (mut session, _) = session();
+  session.guess(2, 5, b"me by").unwrap();
+  let path = std::env::temp_dir().join(format!("crib_session_{}.txt", std::process::id()));
+  let path = path.to_str().unwrap();
+  session.save(path).unwrap();
+  let loaded = CribSession::load(path).unwrap();
+  assert!(loaded.ciphertexts() == session.ciphertexts());
+  assert!(loaded.keystream() == session.keystream());
+  std::fs::write(path, "00 11\nzz\n").unwrap();
+  assert!(CribSession::load(path).is_err());
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn commands() {
+  let (mut session, _) = session();
+  let input =
+    "guess 1 0 we attack\nundo\nundo\nset 0 zz\nfrobnicate\ndrag castle\nrevert 3..1\nquit\nshow\n";
+  let mut output = vec![];
+  crib_drag::run(&mut session, &NgramLogLikelihood::trigrams(), input.as_bytes(), &mut output)
+    .unwrap();
+  let output = String::from_utf8(output).unwrap();
+  assert!(output.contains("00 | the quick·"));
+  assert!(output.contains("Nothing to undo"));
+  assert!(output.contains("Invalid byte 'zz'"));
+  assert!(output.contains("Unknown command 'frobnicate'"));
+  assert!(output.contains("line 1 offset 14"));
+  assert!(output.contains("Empty column range"));
+  // nothing runs after quit
+  assert!(output.matches("     0         10").count() == 3);
+  assert!(session.keystream().iter().all(|v| v.is_none()));
+}
+
+fn challenge_19() -> (Vec<Vec<u8>>, Vec<Vec<u8>>, Vec<u8>) {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let plaintexts: Vec<Vec<u8>> = std::fs::read_to_string("res/challenge_19.txt")
+    .unwrap()
+    .lines()
+    .map(|v| base64::STANDARD.decode(v).unwrap())
+    .collect();
+  let ciphertexts = plaintexts.iter().map(|v| cryptog::aes_ctr(0, &key, v)).collect();
+  let keystream = cryptog::aes_ctr(0, &key, &[0; 64]);
+  (plaintexts, ciphertexts, keystream)
+}
+
+// auto_crib_drag only keeps fragments that read as prose
+#[test]
+fn challenge_texts_are_prose() {
+  let prose = Charset::prose();
+  for path in ["res/challenge_19.txt", "res/challenge_20.txt"] {
+    let plaintexts: Vec<Vec<u8>> = std::fs::read_to_string(path)
+      .unwrap()
+      .lines()
+      .map(|v| base64::STANDARD.decode(v).unwrap())
+      .collect();
+    assert!(plaintexts.iter().all(|v| prose.accepts(v)));
+  }
+  assert!(prose.accepts(b"Yo Rakim, what's up? / Yo, I'm doing the knowledge"));
+}
+
+#[test]
+fn crib_placements_found() {
+  let (plaintexts, ciphertexts, _) = challenge_19();
+  let cribs = crib_drag::cribs(&WordDictionary::english(), 3);
+  assert!(cribs.contains(&b" the ".to_vec()) && cribs.contains(&b"The ".to_vec()));
+  let placements = crib_placements(&ciphertexts, &cribs, &NgramLogLikelihood::trigrams());
+  assert!(placements.windows(2).all(|v| v[0].score >= v[1].score));
+  // most of the best placements are really there
+  let right = placements
+    .iter()
+    .take(20)
+    .filter(|v| plaintexts[v.line][v.offset..].starts_with(&v.crib))
+    .count();
+  assert!(right >= 18);
+}
+
+#[test]
+fn auto_crib_drag_challenge_19() {
+  let (plaintexts, ciphertexts, keystream) = challenge_19();
+  let cribs = crib_drag::cribs(&WordDictionary::english(), 3);
+  let found = auto_crib_drag(&ciphertexts, &cribs, &NgramLogLikelihood::trigrams());
+  assert!(found.keystream.len() == 38 && found.known() == 38);
+  assert!(!found.placements.is_empty());
+  // every column reached by at least four lines is right
+  for (column, byte) in found.keystream.iter().enumerate() {
+    if ciphertexts.iter().filter(|v| v.len() > column).count() >= 4 {
+      assert!(*byte == Some(keystream[column]));
+    }
+  }
+  assert!(
+    found.decrypt(&ciphertexts[0]) == plaintexts[0].iter().map(|v| Some(*v)).collect::<Vec<_>>()
+  );
+  // the session only fills what is still unknown
+  let mut session = CribSession::new(ciphertexts);
+  session.set(0, 0).unwrap();
+  session.auto(&cribs, &NgramLogLikelihood::trigrams());
+  assert!(session.keystream()[0] == Some(0) && session.keystream()[1] == Some(keystream[1]));
+  session.undo().unwrap();
+  assert!(session.keystream()[1].is_none());
+}
@@ -0,0 +1,101 @@
+use cryptopals_challeges_rs::{
+  bignum,
+  dlog::{self, MultiplicativeGroup},
+  ec,
+  oracle::{ECDH_BOB_MESSAGE, oracle_dh_bob},
+  oracle_hacker::subgroup_confinement_attack,
+};
+use openssl::bn::BigNum;
+
+const P_58: &str = "11470374874925275658116663507232161402086650258453896274534991676898999262641581519101074740642369848233294239851519212341844337347119899874391456329785623";
+const G_58: &str = "622952335333961296978159266084741085889881358738459939978290179936063635566740258555167783009058567397963466103140082647486611657350811560630587013183357";
+const Y_58: &str = "7760073848032689505395005705677365876654629189298052775754597607446617558600394076764814236081991643094239886772481052254010323780165093955236429914607119";
+
+#[test]
+fn baby_step_giant_step() {
+  let params = dlog::cryptopals_params().unwrap();
+  let group = MultiplicativeGroup { p: params.p.to_owned().unwrap() };
+  let bound = BigNum::from_u32(1 << 20).unwrap();
+  let k = bignum::random_below(&bound).unwrap();
+  let target = bignum::mod_exp(&params.g, &k, &params.p).unwrap();
+  assert!(dlog::baby_step_giant_step(&group, &params.g, &target, &bound).unwrap() == Some(k));
+  let curve = ec::cryptopals_params().unwrap();
+  let k = bignum::random_below(&bound).unwrap();
+  let target = curve.curve.scalar_mul(&k, &curve.base).unwrap();
+  let res = dlog::baby_step_giant_step(&curve.curve, &curve.base, &target, &bound).unwrap();
+  assert!(res == Some(k));
+}
+
+#[test]
+fn kangaroo_mod_p() {
+  let p = BigNum::from_dec_str(P_58).unwrap();
+  let g = BigNum::from_dec_str(G_58).unwrap();
+  let group = MultiplicativeGroup { p: p.to_owned().unwrap() };
+  let lower = BigNum::new().unwrap();
+  let upper = BigNum::from_u32(1 << 20).unwrap();
+  let target = BigNum::from_dec_str(Y_58).unwrap();
+  let res = dlog::kangaroo(&group, &g, &target, &lower, &upper).unwrap().unwrap();
+  assert!(bignum::mod_exp(&g, &res, &p).unwrap() == target);
+  let k = bignum::random_below(&upper).unwrap();
+  let target = bignum::mod_exp(&g, &k, &p).unwrap();
+  assert!(dlog::kangaroo(&group, &g, &target, &lower, &upper).unwrap() == Some(k));
+}
+
+#[test]
+fn kangaroo_ec() {
+  let params = ec::cryptopals_params().unwrap();
+  let lower = BigNum::from_u32(1 << 20).unwrap();
+  let upper = BigNum::from_u32(1 << 22).unwrap();
+  let k = &bignum::random_below(&(&upper - &lower)).unwrap() + &lower;
+  let target = params.curve.scalar_mul(&k, &params.base).unwrap();
+  let res = dlog::kangaroo(&params.curve, &params.base, &target, &lower, &upper).unwrap();
+  assert!(res == Some(k));
+}
+
+#[test]
+fn challenge_57() {
+  let params = dlog::cryptopals_params().unwrap();
+  let (secret, _) = dlog::generate_keypair(&params).unwrap();
+  let res = subgroup_confinement_attack(&params, 1 << 16, ECDH_BOB_MESSAGE, |h| {
+    oracle_dh_bob(h, &secret, &params)
+  });
+  assert!(res == Some(secret));
+}
+
+#[test]
+fn pohlig_hellman() {
+  let (p, factors) = bignum::smooth_prime(128, &[]).unwrap();
+  let group = MultiplicativeGroup { p: p.to_owned().unwrap() };
+  let mut factors: Vec<(u32, u32)> = factors.into_iter().map(|r| (r, 1)).collect();
+  factors.push((2, 1));
+  let g = BigNum::from_u32(3).unwrap();
+  let order = &p - &BigNum::from_u32(1).unwrap();
+  let k = bignum::random_below(&order).unwrap();
+  let target = bignum::mod_exp(&g, &k, &p).unwrap();
+  let res = dlog::pohlig_hellman(&group, &g, &target, &factors).unwrap().unwrap();
+  assert!(bignum::mod_exp(&g, &res, &p).unwrap() == target);
+}
+
+#[test]
+fn pohlig_hellman_prime_power_past_u32() {
+  // 9 * 2^33 + 1, where 7^9 generates the subgroup of order 2^33
+  let p = BigNum::from_dec_str("77309411329").unwrap();
+  let group = MultiplicativeGroup { p: p.to_owned().unwrap() };
+  let g = BigNum::from_u32(40353607).unwrap();
+  let k = bignum::from_u64(0x1_2345_6789).unwrap();
+  let target = bignum::mod_exp(&g, &k, &p).unwrap();
+  let res = dlog::pohlig_hellman(&group, &g, &target, &[(2, 33)]).unwrap();
+  assert!(res == Some(k));
+}
+
+#[test]
+fn range_past_64_bits() {
+  let params = dlog::cryptopals_params().unwrap();
+  let group = MultiplicativeGroup { p: params.p.to_owned().unwrap() };
+  let lower = BigNum::new().unwrap();
+  let mut upper = BigNum::new().unwrap();
+  upper.set_bit(70).unwrap();
+  let target = bignum::mod_exp(&params.g, &upper, &params.p).unwrap();
+  assert!(dlog::baby_step_giant_step(&group, &params.g, &target, &upper).unwrap().is_none());
+  assert!(dlog::kangaroo(&group, &params.g, &target, &lower, &upper).unwrap().is_none());
+}
@@ -0,0 +1,77 @@
+use cryptopals_challeges_rs::dsa::{self, DsaSignature};
+use openssl::bn::BigNum;
+
+#[test]
+fn challenge_43() {
+  let params = dsa::cryptopals_params().unwrap();
+  let y = BigNum::from_hex_str("84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4abab3e4bdebf2955b4736012f21a08084056b19bcd7fee56048e004e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0fb2ed1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07bbb283e6633451e535c45513b2d33c99ea17").unwrap();
+  let message = "For those that envy a MC it can be hazardous to your health\nSo be friendly, a matter of life and death, just like a etch-a-sketch\n";
+  let hash = dsa::hash_message(message.as_bytes()).unwrap();
+  assert!(hash == BigNum::from_hex_str("d2d0714f014a9784047eaeccf956520045c45265").unwrap());
+  let signature = DsaSignature {
+    r: BigNum::from_dec_str("548099063082341131477253921760299949438196259240").unwrap(),
+    s: BigNum::from_dec_str("857042759984254168557880549501802188789837994940").unwrap(),
+  };
+  assert!(dsa::verify(&params, &y, message.as_bytes(), &signature).unwrap());
+  let (_, x) = dsa::brute_force_nonce(&params, &y, &hash, &signature, 1 << 16).unwrap().unwrap();
+  assert!(dsa::key_fingerprint(&x).unwrap() == "0954edd5e0afe5542a4adf012611a91912a3ec16");
+}
+
+#[test]
+fn sign_and_verify() {
+  let params = dsa::cryptopals_params().unwrap();
+  let keypair = dsa::generate_keypair(&params).unwrap();
+  let signature = dsa::sign(&params, &keypair.x, "I AM HERE".as_bytes()).unwrap();
+  assert!(dsa::verify(&params, &keypair.y, "I AM HERE".as_bytes(), &signature).unwrap());
+  assert!(!dsa::verify(&params, &keypair.y, "I AM THERE".as_bytes(), &signature).unwrap());
+}
+
+#[test]
+fn challenge_44() {
+  let params = dsa::cryptopals_params().unwrap();
+  let y = BigNum::from_hex_str("9e77fa07bd80a683d3846353f925979a47e27adde13c26eac1e9b4c54485e603b75b281d271c39bdc7b959083c90f7634c64ac6ec0feb160080932b20902b572d4164d8ae9aee9380f059c3b14b6d6e8e6f57d5b619e4f760ce401b240d07df5e95d6234035065c63775094a90f6ce505a72614b1e436bba5393be884ebc86").unwrap();
+  let messages = dsa::read_signed_messages("./res/challenge_44.txt").unwrap();
+  assert!(messages.len() == 11);
+  for m in messages.iter() {
+    assert!(dsa::hash_message(m.message.as_bytes()).unwrap() == m.hash);
+    assert!(dsa::verify(&params, &y, m.message.as_bytes(), &m.signature).unwrap());
+  }
+  let x = dsa::recover_x_repeated_nonce(&params, &y, &messages).unwrap().unwrap();
+  assert!(dsa::key_fingerprint(&x).unwrap() == "778f888d043d5ada418357149e71cddf7aa8d64d");
+}
+
+#[test]
+fn read_signed_messages_malformed() {
+  let path = std::env::temp_dir().join(format!("signed_malformed_{}.txt", std::process::id()));
+  // a trailing partial block
+  std::fs::write(&path, "msg: Hello\ns: 1\nr: 2\nm: 3\nmsg: World\ns: 4\n").unwrap();
+  let res = dsa::read_signed_messages(path.to_str().unwrap());
+  std::fs::remove_file(&path).unwrap();
+  assert!(res.unwrap_err().kind() == std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn challenge_45_g_zero() {
+  let mut params = dsa::cryptopals_params().unwrap();
+  params.g = BigNum::from_u32(0).unwrap();
+  let keypair = dsa::generate_keypair(&params).unwrap();
+  let signature = dsa::sign(&params, &keypair.x, "Hello, world".as_bytes()).unwrap();
+  assert!(signature.r.num_bits() == 0);
+  let magic = dsa::magic_signature_g_zero().unwrap();
+  for message in ["Hello, world", "Goodbye, world"] {
+    assert!(dsa::verify_unchecked(&params, &keypair.y, message.as_bytes(), &signature).unwrap());
+    assert!(dsa::verify_unchecked(&params, &keypair.y, message.as_bytes(), &magic).unwrap());
+    assert!(!dsa::verify(&params, &keypair.y, message.as_bytes(), &magic).unwrap());
+  }
+}
+
+#[test]
+fn challenge_45_g_p_plus_one() {
+  let mut params = dsa::cryptopals_params().unwrap();
+  let keypair = dsa::generate_keypair(&params).unwrap();
+  params.g = &params.p + &BigNum::from_u32(1).unwrap();
+  let z = BigNum::from_u32(7).unwrap();
+  let magic = dsa::magic_signature_g_p_plus_one(&params, &keypair.y, &z).unwrap();
+  assert!(dsa::verify(&params, &keypair.y, "Hello, world".as_bytes(), &magic).unwrap());
+  assert!(dsa::verify(&params, &keypair.y, "Goodbye, world".as_bytes(), &magic).unwrap());
+}
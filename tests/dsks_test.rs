@@ -0,0 +1,51 @@
+use cryptopals_challeges_rs::{bignum, oracle_hacker::dsks_attack, rsa};
+use openssl::bn::BigNum;
+
+#[test]
+fn sign_and_verify() {
+  let (public, private) = rsa::generate_keypair(1024, 65537).unwrap();
+  let signature = rsa::sign(&private, b"hi mom").unwrap();
+  assert!(signature.len() == 128);
+  assert!(rsa::verify(&public, b"hi mom", &signature).unwrap());
+  assert!(!rsa::verify(&public, b"hi dad", &signature).unwrap());
+  let mut tampered = signature.clone();
+  tampered[5] ^= 1;
+  assert!(!rsa::verify(&public, b"hi mom", &tampered).unwrap());
+}
+
+#[test]
+fn smooth_prime() {
+  let (p, factors) = bignum::smooth_prime(256, &[]).unwrap();
+  assert!(p.num_bits() == 256);
+  let mut product = BigNum::from_u32(2).unwrap();
+  for r in &factors {
+    assert!(*r < u32::MAX && BigNum::from_u32(*r).unwrap().num_bits() <= 32);
+    product = &product * &BigNum::from_u32(*r).unwrap();
+  }
+  assert!(&product + &BigNum::from_u32(1).unwrap() == p);
+  let (_, other) = bignum::smooth_prime(256, &factors).unwrap();
+  assert!(other.iter().all(|r| !factors.contains(r)));
+}
+
+fn run_attack(bits: i32) {
+  let (public, private) = rsa::generate_keypair(bits, 65537).unwrap();
+  let message = b"I owe Eve nothing";
+  let forged = b"I owe Eve a million dollars";
+  let signature = rsa::sign(&private, message).unwrap();
+  let (evil_public, evil_private) = dsks_attack(message, &signature, &public, forged).unwrap();
+  assert!(evil_public.n != public.n);
+  assert!(rsa::verify(&evil_public, forged, &signature).unwrap());
+  assert!(rsa::sign(&evil_private, forged).unwrap() == signature);
+}
+
+// challenge 61 with a key small enough for the default run
+#[test]
+fn dsks_512() {
+  run_attack(512);
+}
+
+#[test]
+#[ignore = "the discrete logs for a 1024 bit key can take half a minute, run with --ignored"]
+fn challenge_61_rsa() {
+  run_attack(1024);
+}
@@ -0,0 +1,184 @@
+use cryptopals_challeges_rs::{
+  bignum,
+  ec::{self, EcPoint},
+  oracle::{ECDH_BOB_MESSAGE, oracle_ecdh_bob, oracle_ecdh_bob_x_only},
+  oracle_hacker::{invalid_curve_attack, twist_attack, twist_attack_bounded, twist_residues},
+};
+use openssl::bn::BigNum;
+
+const INVALID_CURVES: [(u32, &str); 3] = [
+  (210, "233970423115425145550826547352470124412"),
+  (504, "233970423115425145544350131142039591210"),
+  (727, "233970423115425145545378039958152057148"),
+];
+
+#[test]
+fn base_point() {
+  let params = ec::cryptopals_params().unwrap();
+  assert!(params.curve.contains(&params.base).unwrap());
+  assert!(params.curve.scalar_mul(&params.order, &params.base).unwrap() == EcPoint::Infinity);
+  assert!(params.curve.ladder(&params.order, &params.base).unwrap() == EcPoint::Infinity);
+}
+
+#[test]
+fn point_arithmetic() {
+  let curve = ec::cryptopals_params().unwrap().curve;
+  let point = curve.random_point().unwrap();
+  assert!(curve.contains(&point).unwrap());
+  assert!(curve.add(&point, &curve.neg(&point).unwrap()).unwrap() == EcPoint::Infinity);
+  assert!(curve.add(&point, &EcPoint::Infinity).unwrap() == point);
+  let mut sum = EcPoint::Infinity;
+  for k in 0..20u32 {
+    let k_bn = BigNum::from_u32(k).unwrap();
+    assert!(curve.scalar_mul(&k_bn, &point).unwrap() == sum);
+    assert!(curve.ladder(&k_bn, &point).unwrap() == sum);
+    sum = curve.add(&sum, &point).unwrap();
+  }
+  assert!(curve.double(&point).unwrap() == curve.add(&point, &point).unwrap());
+  let k = bignum::random_below(&curve.p).unwrap();
+  let projective = curve.scalar_mul(&k, &point).unwrap();
+  assert!(curve.contains(&projective).unwrap());
+  assert!(projective == curve.ladder(&k, &point).unwrap());
+}
+
+#[test]
+fn ecdh() {
+  let params = ec::cryptopals_params().unwrap();
+  let (alice_secret, alice_public) = ec::generate_keypair(&params).unwrap();
+  let (bob_secret, bob_public) = ec::generate_keypair(&params).unwrap();
+  let alice_shared = ec::shared_secret(&params.curve, &alice_secret, &bob_public).unwrap();
+  let bob_shared = ec::shared_secret(&params.curve, &bob_secret, &alice_public).unwrap();
+  assert!(alice_shared == bob_shared);
+  assert!(ec::shared_key(&alice_shared) == ec::shared_key(&bob_shared));
+}
+
+#[test]
+fn invalid_curve_orders() {
+  let params = ec::cryptopals_params().unwrap();
+  for (b, order) in INVALID_CURVES {
+    let curve = params.curve.with_b(&BigNum::from_u32(b).unwrap()).unwrap();
+    let order = BigNum::from_dec_str(order).unwrap();
+    let point = curve.random_point().unwrap();
+    assert!(curve.contains(&point).unwrap() && !params.curve.contains(&point).unwrap());
+    assert!(curve.scalar_mul(&order, &point).unwrap() == EcPoint::Infinity);
+    for r in bignum::small_factors(&order, 1000).unwrap() {
+      let factor_point = curve.point_of_order(&order, r).unwrap();
+      let r = BigNum::from_u32(r).unwrap();
+      assert!(curve.ladder(&r, &factor_point).unwrap() == EcPoint::Infinity);
+    }
+  }
+}
+
+#[test]
+fn challenge_59() {
+  let params = ec::cryptopals_params().unwrap();
+  let (secret, _) = ec::generate_keypair(&params).unwrap();
+  let orders: Vec<BigNum> =
+    INVALID_CURVES.iter().map(|(_, order)| BigNum::from_dec_str(order).unwrap()).collect();
+  let curves: Vec<_> = INVALID_CURVES.iter().zip(&orders).map(|((b, _), o)| (*b, &**o)).collect();
+  let res = invalid_curve_attack(&params, &curves, 1 << 16, ECDH_BOB_MESSAGE, |point| {
+    oracle_ecdh_bob(point, &secret, &params)
+  });
+  assert!(res == Some(secret));
+}
+
+#[test]
+fn invalid_curve_attack_lost_answer() {
+  let params = ec::cryptopals_params().unwrap();
+  let (secret, _) = ec::generate_keypair(&params).unwrap();
+  let orders: Vec<BigNum> =
+    INVALID_CURVES.iter().map(|(_, order)| BigNum::from_dec_str(order).unwrap()).collect();
+  let curves: Vec<_> = INVALID_CURVES.iter().zip(&orders).map(|((b, _), o)| (*b, &**o)).collect();
+  // the first answer is garbage, its factor must not count towards the CRT
+  let queries = std::cell::Cell::new(0);
+  let res = invalid_curve_attack(&params, &curves, 1 << 16, ECDH_BOB_MESSAGE, |point| {
+    queries.set(queries.get() + 1);
+    match queries.get() {
+      1 => vec![0; 32],
+      _ => oracle_ecdh_bob(point, &secret, &params),
+    }
+  });
+  assert!(res == Some(secret));
+}
+
+#[test]
+fn montgomery_ladder() {
+  let params = ec::cryptopals_montgomery_params().unwrap();
+  let weierstrass = ec::cryptopals_params().unwrap();
+  assert!(params.curve.to_weierstrass().unwrap().a == weierstrass.curve.a);
+  assert!(params.curve.to_weierstrass().unwrap().b == weierstrass.curve.b);
+  assert!(params.curve.to_weierstrass_point(&params.base).unwrap().is_some_and(|v| {
+    v == weierstrass.base || v == weierstrass.curve.neg(&weierstrass.base).unwrap()
+  }));
+  assert!(params.curve.ladder(&params.base, &params.order).unwrap().num_bits() == 0);
+  let k = bignum::random_below(&params.order).unwrap();
+  let u = params.curve.ladder(&params.base, &k).unwrap();
+  let EcPoint::Affine { x, .. } = weierstrass.curve.scalar_mul(&k, &weierstrass.base).unwrap()
+  else {
+    panic!("k is below the order");
+  };
+  assert!(&u + &BigNum::from_u32(178).unwrap() == x);
+}
+
+#[test]
+fn twist_points() {
+  let params = ec::cryptopals_montgomery_params().unwrap();
+  let twist_order = params.curve.twist_order(&params.group_order).unwrap();
+  let u = params.curve.random_twist_point().unwrap();
+  assert!(!params.curve.on_curve(&u).unwrap());
+  assert!(params.curve.ladder(&u, &twist_order).unwrap().num_bits() == 0);
+  let u = params.curve.twist_point_of_order(&twist_order, &[11, 107]).unwrap();
+  assert!(params.curve.ladder(&u, &BigNum::from_u32(1177).unwrap()).unwrap().num_bits() == 0);
+  assert!(params.curve.ladder(&u, &BigNum::from_u32(107).unwrap()).unwrap().num_bits() != 0);
+}
+
+#[test]
+fn kangaroo() {
+  let params = ec::cryptopals_params().unwrap();
+  let lower = BigNum::from_u32(1 << 20).unwrap();
+  let upper = BigNum::from_u32(1 << 22).unwrap();
+  let k = &bignum::random_below(&(&upper - &lower)).unwrap() + &lower;
+  let target = params.curve.scalar_mul(&k, &params.base).unwrap();
+  let res = ec::kangaroo(&params.curve, &params.base, &target, &lower, &upper).unwrap();
+  assert!(res == Some(k));
+}
+
+#[test]
+fn twist_residues_match() {
+  let params = ec::cryptopals_montgomery_params().unwrap();
+  let secret = bignum::random_below(&params.order).unwrap();
+  let (residue, modulus) = twist_residues(&params, 2000, ECDH_BOB_MESSAGE, |u| {
+    oracle_ecdh_bob_x_only(u, &secret, &params)
+  });
+  // 11 * 107 * 197 * 1621
+  assert!(modulus == BigNum::from_dec_str("375859649").unwrap());
+  let expected = bignum::nnmod(&secret, &modulus).unwrap();
+  assert!(residue == expected || &modulus - &residue == expected);
+}
+
+// challenge 60 scaled down: the twist factors below 2000 and a secret below 2^40
+#[test]
+fn twist_attack_small_secret() {
+  let params = ec::cryptopals_montgomery_params().unwrap();
+  let bound = &BigNum::from_u32(1).unwrap() << 40;
+  let secret = bignum::random_below(&bound).unwrap();
+  let public = params.curve.ladder(&params.base, &secret).unwrap();
+  let res = twist_attack_bounded(&params, &public, 2000, &bound, ECDH_BOB_MESSAGE, |u| {
+    oracle_ecdh_bob_x_only(u, &secret, &params)
+  })
+  .unwrap();
+  assert!(res == secret);
+}
+
+#[test]
+#[ignore = "brute forces a subgroup of order 2323367 and runs kangaroo over 2^40"]
+fn challenge_60() {
+  let params = ec::cryptopals_montgomery_params().unwrap();
+  let secret = bignum::random_below(&params.order).unwrap();
+  let public = params.curve.ladder(&params.base, &secret).unwrap();
+  let res = twist_attack(&params, &public, 1 << 22, ECDH_BOB_MESSAGE, |u| {
+    oracle_ecdh_bob_x_only(u, &secret, &params)
+  })
+  .unwrap();
+  assert!(res == secret || res == &params.order - &secret);
+}
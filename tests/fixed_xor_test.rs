@@ -1,9 +1,9 @@
-use cryptopals_challeges_rs::xor;
+use cryptopals_challeges_rs::{htb64::hex, xor};
 
 
 #[test]
 fn xor_mismatch_sizes() {
-  assert!(xor::xor_fixed_length(&vec![10,20,30], &vec![50]).is_err())
+  assert!(xor::xor_fixed_length(&[10,20,30], &[50]).is_err())
 }
 
 #[test]
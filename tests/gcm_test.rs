@@ -0,0 +1,135 @@
+use cryptopals_challeges_rs::{
+  gcm::{self, GcmMessage, InvalidTag},
+  gf128::Gf128,
+  htb64::hex,
+};
+use openssl::symm::{Cipher, encrypt_aead};
+
+fn nist_case(key: &str, nonce: &str, plaintext: &str, aad: &str, ciphertext: &str, tag: &str) {
+  let [key, nonce, plaintext, aad] = [key, nonce, plaintext, aad].map(|v| hex::decode(v).unwrap());
+  let (res, res_tag) = gcm::aes_gcm_encrypt(&key, &nonce, &plaintext, &aad);
+  assert!(hex::encode(&res) == ciphertext);
+  assert!(hex::encode(&res_tag) == tag);
+  assert!(gcm::aes_gcm_decrypt(&key, &nonce, &res, &aad, &res_tag) == Ok(plaintext));
+}
+
+#[test]
+fn nist_vectors() {
+  let zero_key = "00000000000000000000000000000000";
+  let zero_nonce = "000000000000000000000000";
+  nist_case(zero_key, zero_nonce, "", "", "", "58e2fccefa7e3061367f1d57a4e7455a");
+  nist_case(
+    zero_key,
+    zero_nonce,
+    "00000000000000000000000000000000",
+    "",
+    "0388dace60b6a392f328c2b971b2fe78",
+    "ab6e47d42cec13bdf53a67b21257bddf",
+  );
+  let key = "feffe9928665731c6d6a8f9467308308";
+  let nonce = "cafebabefacedbaddecaf888";
+  let plaintext = concat!(
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+    "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+  );
+  let ciphertext = concat!(
+    "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+    "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091"
+  );
+  nist_case(
+    key,
+    nonce,
+    &format!("{plaintext}1aafd255"),
+    "",
+    &format!("{ciphertext}473f5985"),
+    "4d5c2af327cd64a62cf35abd2ba6fab4",
+  );
+  nist_case(
+    key,
+    nonce,
+    plaintext,
+    "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+    ciphertext,
+    "5bc94fbc3221a5db94fae95ae7121a47",
+  );
+}
+
+#[test]
+fn matches_openssl() {
+  for len in [0, 1, 15, 16, 17, 64, 100] {
+    let key: Vec<u8> = rand::random_iter().take(16).collect();
+    let nonce: Vec<u8> = rand::random_iter().take(12).collect();
+    let plaintext: Vec<u8> = rand::random_iter().take(len).collect();
+    let aad: Vec<u8> = rand::random_iter().take(len / 3).collect();
+    let mut expected_tag = vec![0; 16];
+    let expected =
+      encrypt_aead(Cipher::aes_128_gcm(), &key, Some(&nonce), &aad, &plaintext, &mut expected_tag)
+        .unwrap();
+    assert!(gcm::aes_gcm_encrypt(&key, &nonce, &plaintext, &aad) == (expected, expected_tag));
+  }
+}
+
+#[test]
+fn rejects_tampering() {
+  let key = b"YELLOW SUBMARINE";
+  let nonce = b"twelve bytes";
+  let (mut ciphertext, tag) = gcm::aes_gcm_encrypt(key, nonce, b"attack at dawn", b"header");
+  assert!(gcm::aes_gcm_decrypt(key, nonce, &ciphertext, b"headex", &tag) == Err(InvalidTag {}));
+  assert!(
+    gcm::aes_gcm_decrypt(key, nonce, &ciphertext, b"header", &tag[..15]) == Err(InvalidTag {})
+  );
+  ciphertext[0] ^= 1;
+  assert!(gcm::aes_gcm_decrypt(key, nonce, &ciphertext, b"header", &tag) == Err(InvalidTag {}));
+}
+
+#[test]
+fn challenge_63() {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let nonce: Vec<u8> = rand::random_iter().take(12).collect();
+  let seal = |plaintext: &[u8], aad: &[u8]| {
+    let (ciphertext, tag) = gcm::aes_gcm_encrypt(&key, &nonce, plaintext, aad);
+    GcmMessage { aad: aad.to_owned(), ciphertext, tag }
+  };
+  let first = seal(b"Transfer 100 to Alice, regards from Bob", b"msg-1");
+  let second = seal(b"Transfer 200 to Carol, regards from Bob", b"msg-2");
+  let candidates = gcm::forbidden_nonce_candidates(&first, &second);
+  assert!(candidates.contains(&gcm::auth_key(&key)));
+  // a third message under the same nonce weeds out the wrong roots
+  let third = seal(b"Lunch at noon?", b"msg-3");
+  let h: Vec<Gf128> = candidates
+    .into_iter()
+    .filter(|h| gcm::forge_tag(*h, &first, &third.aad, &third.ciphertext) == third.tag)
+    .collect();
+  assert!(h == [gcm::auth_key(&key)]);
+  let mut forged = first.ciphertext.clone();
+  forged[9] ^= b'1' ^ b'9';
+  let tag = gcm::forge_tag(h[0], &first, b"msg-1", &forged);
+  let plaintext = gcm::aes_gcm_decrypt(&key, &nonce, &forged, b"msg-1", &tag).unwrap();
+  assert!(plaintext == b"Transfer 900 to Alice, regards from Bob");
+}
+
+#[test]
+fn truncated_tags() {
+  let key = b"YELLOW SUBMARINE";
+  let nonce = b"twelve bytes";
+  let (ciphertext, tag) = gcm::aes_gcm_encrypt_truncated(key, nonce, b"attack at dawn", b"", 4);
+  let (_, full_tag) = gcm::aes_gcm_encrypt(key, nonce, b"attack at dawn", b"");
+  assert!(tag == full_tag[..4]);
+  assert!(gcm::aes_gcm_decrypt_truncated(key, nonce, &ciphertext, b"", &tag, 4).is_ok());
+  assert!(gcm::aes_gcm_decrypt_truncated(key, nonce, &ciphertext, b"", &full_tag, 4).is_err());
+  assert!(gcm::aes_gcm_decrypt(key, nonce, &ciphertext, b"", &tag).is_err());
+}
+
+#[test]
+fn challenge_64() {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let nonce: Vec<u8> = rand::random_iter().take(12).collect();
+  // 2^8 blocks and 16-bit tags keep the number of oracle queries small
+  let plaintext: Vec<u8> = rand::random_iter().take(16 << 8).collect();
+  let (ciphertext, tag) = gcm::aes_gcm_encrypt_truncated(&key, &nonce, &plaintext, b"", 2);
+  let oracle_fn = |ciphertext: &[u8], tag: &[u8]| {
+    gcm::aes_gcm_decrypt_truncated(&key, &nonce, ciphertext, b"", tag, 2).is_ok()
+  };
+  let h = gcm::ferguson_attack(&ciphertext, &tag, oracle_fn);
+  assert!(h == gcm::auth_key(&key));
+}
@@ -0,0 +1,55 @@
+use cryptopals_challeges_rs::gf128::{Gf128, Poly};
+
+#[test]
+fn field_arithmetic() {
+  let (a, b, c) = (Gf128::random(), Gf128::random(), Gf128::random());
+  assert!(a * b == b * a);
+  assert!(a * (b + c) == a * b + a * c);
+  assert!((a * b) * c == a * (b * c));
+  assert!(a * Gf128::ONE == a && a + a == Gf128::ZERO);
+  assert!(a * a.inverse() == Gf128::ONE);
+  assert!((a / b) * b == a);
+  assert!(a.sqrt() * a.sqrt() == a);
+  // x * x = x^2 in GCM's reflected bit order
+  assert!(Gf128(1 << 126) * Gf128(1 << 126) == Gf128(1 << 125));
+  assert!(Gf128::from_bytes(&[0x80]) == Gf128::ONE);
+}
+
+#[test]
+fn poly_arithmetic() {
+  let (f, g) = (Poly::random(5), Poly::random(3));
+  let (quotient, rem) = f.div_rem(&g);
+  assert!(quotient.mul(&g).add(&rem) == f);
+  assert!(rem.degree() < g.degree());
+  let common = Poly::random(2).monic();
+  assert!(f.mul(&common).gcd(&g.mul(&common)) == common);
+  let x = Gf128::random();
+  assert!(f.mul(&g).eval(x) == f.eval(x) * g.eval(x));
+}
+
+#[test]
+fn factorization() {
+  let (a, b, c) = (Gf128::random(), Gf128::random(), Gf128::random());
+  let quadratic = Poly::random(2).monic();
+  let f = Poly::linear(a)
+    .mul(&Poly::linear(b))
+    .mul(&Poly::linear(b))
+    .mul(&Poly::linear(c))
+    .mul(&Poly::linear(c))
+    .mul(&Poly::linear(c))
+    .mul(&quadratic)
+    .scale(Gf128::random());
+  let factors = f.factor();
+  let product =
+    factors.iter().fold(Poly::one(), |acc, (v, m)| (0..*m).fold(acc, |acc, _| acc.mul(v)));
+  assert!(product == f.monic());
+  assert!(factors.contains(&(Poly::linear(a), 1)));
+  assert!(factors.contains(&(Poly::linear(b), 2)));
+  assert!(factors.contains(&(Poly::linear(c), 3)));
+  let mut roots = f.roots();
+  roots.sort_by_key(|v| v.0);
+  let mut expected = vec![a, b, c];
+  expected.extend(quadratic.roots());
+  expected.sort_by_key(|v| v.0);
+  assert!(roots == expected);
+}
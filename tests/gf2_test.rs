@@ -0,0 +1,28 @@
+use cryptopals_challeges_rs::{
+  gf2::{self, BitMatrix},
+  gf128::Gf128,
+};
+
+#[test]
+fn field_matrices() {
+  let (c, v) = (Gf128::random(), Gf128::random());
+  assert!(gf2::mul_matrix(c).mul_gf128(v) == c * v);
+  assert!(gf2::square_matrix().mul_gf128(v) == v * v);
+  assert!(gf2::mul_matrix(c).mul(&gf2::square_matrix()).mul_gf128(v) == c * v * v);
+  assert!(gf2::from_column(&gf2::to_column(v), 0) == v);
+}
+
+#[test]
+fn kernel() {
+  let mut m = BitMatrix::zero(24, 40);
+  for (row, col) in (0..24).flat_map(|r| (0..40).map(move |c| (r, c))) {
+    m.set(row, col, rand::random());
+  }
+  // a duplicated row doesn't add to the rank
+  let m = m.stack(&m.row(3));
+  let kernel = m.kernel();
+  assert!(m.rank() <= 24 && kernel.rows() == 40 - m.rank());
+  assert!(m.mul(&kernel.transpose()).is_zero());
+  assert!(kernel.rank() == kernel.rows());
+  assert!(BitMatrix::identity(128).kernel().rows() == 0);
+}
@@ -0,0 +1,69 @@
+use std::io::{Read, Write};
+
+use cryptopals_challeges_rs::htb64::{
+  self,
+  hex::{self, Case, HexReader, HexWriter},
+};
+
+#[test]
+fn encode_case() {
+  let bytes = [0x00, 0x4a, 0xff, 0x10];
+  assert!(hex::encode(bytes) == "004aff10");
+  assert!(hex::encode_with(bytes, Case::Upper) == "004AFF10");
+  assert!(htb64::bytes_to_hex(&bytes) == "004AFF10");
+  let mut out = [0; 8];
+  assert!(hex::encode_to_slice(&bytes, &mut out, Case::Lower).unwrap() == "004aff10");
+  assert!(hex::encode_to_slice(&bytes, &mut [0; 7], Case::Lower).is_err());
+}
+
+#[test]
+fn decode_strict_and_tolerant() {
+  assert!(hex::decode(b"004aFF10").unwrap() == vec![0x00, 0x4a, 0xff, 0x10]);
+  assert!(hex::decode(b"4a 6f").is_err());
+  assert!(hex::decode(b"4a6").is_err());
+  let expected = vec![0x4a, 0x6f, 0x00];
+  for text in ["4a6f00", "0x4a 0x6f 0x00", "4a:6f:00", "0X4A, 0X6F, 0X00\n", "  4a6f\n00  "] {
+    assert!(hex::decode_tolerant(text.as_bytes()).unwrap() == expected);
+  }
+  // a bare 0 is a digit
+  assert!(hex::decode_tolerant(b"0 a").unwrap() == vec![0x0a]);
+  assert!(hex::decode_tolerant(b"4x").is_err());
+  assert!(hex::decode_tolerant(b"4a 6").is_err());
+}
+
+#[test]
+fn error_positions() {
+  let err = hex::decode_tolerant(b"0x4a 0x6g").unwrap_err();
+  assert!(err.to_string() == "Invalid char for hex string 'g' at position 8");
+  let err = htb64::hex_bytes_to_bytes(b"12z4").unwrap_err();
+  assert!(err.to_string() == "Invalid char for hex string 'z' at position 2");
+}
+
+#[test]
+fn streaming_round_trip() {
+  let bytes: Vec<u8> = (0..5000).map(|v| (v * 7 % 256) as u8).collect();
+  let mut writer = HexWriter::new(vec![], Case::Upper);
+  writer.write_all(&bytes).unwrap();
+  let text = writer.into_inner();
+  assert!(text == hex::encode_with(&bytes, Case::Upper).into_bytes());
+  let mut res = vec![];
+  HexReader::new(&text[..]).read_to_end(&mut res).unwrap();
+  assert!(res == bytes);
+
+  let mut res = vec![];
+  HexReader::tolerant(&b"0x4a 0x6f\n"[..]).read_to_end(&mut res).unwrap();
+  assert!(res == vec![0x4a, 0x6f]);
+  assert!(HexReader::new(&b"4a6"[..]).read_to_end(&mut vec![]).is_err());
+}
+
+#[test]
+fn hexdump_format() {
+  let dump = hex::hexdump(b"I'm back and I'm ringin' the bell\x00");
+  let expected = "\
+00000000: 4927 6d20 6261 636b 2061 6e64 2049 276d  I'm back and I'm
+00000010: 2072 696e 6769 6e27 2074 6865 2062 656c   ringin' the bel
+00000020: 6c00                                     l.
+";
+  assert!(dump == expected);
+  assert!(hex::hexdump(b"").is_empty());
+}
@@ -1,5 +1,4 @@
-use base64::{Engine, prelude::BASE64_STANDARD};
-use cryptopals_challeges_rs::htb64;
+use cryptopals_challeges_rs::htb64::{self, base64, hex};
 use rand::seq::IndexedRandom;
 
 #[test]
@@ -18,8 +17,8 @@ fn invalid_hex_char() {
 fn small_hex() {
   let input = "ABCDEF0123456789";
   let res = htb64::hex_to_base64(input.as_bytes());
-  assert!(!res.is_err());
-  assert!(res.unwrap() == BASE64_STANDARD.encode(hex::decode(input).unwrap()))
+  assert!(res.is_ok());
+  assert!(res.unwrap() == base64::STANDARD.encode(hex::decode(input).unwrap()))
 }
 
 #[test]
@@ -27,18 +26,18 @@ fn big_hex() {
   let symbols: Vec<String> = "0123456789ABCDEFabcdef"
     .as_bytes()
     .iter()
-    .map(|c| String::from_utf8(vec![c.clone()]).unwrap())
+    .map(|c| String::from_utf8(vec![*c]).unwrap())
     .collect();
   let input = (0..40960)
     .map(|_| symbols.choose(&mut rand::rng()).unwrap())
-    .map(|c| c.clone())
+    .cloned()
     .collect::<Vec<_>>()
     .concat();
   dbg!(input.clone());
   let res = htb64::hex_to_base64(input.as_bytes());
-  let real_res = BASE64_STANDARD.encode(hex::decode(input).unwrap());
+  let real_res = base64::STANDARD.encode(hex::decode(input).unwrap());
   dbg!(real_res.clone());
-  assert!(!res.is_err());
+  assert!(res.is_ok());
   let res_string = res.unwrap();
   dbg!(res_string.clone());
   assert!(res_string == real_res)
@@ -0,0 +1,95 @@
+use cryptopals_challeges_rs::md_hash::{self, BLOCK_SIZE, ToyHash};
+
+#[test]
+fn toy_hash() {
+  let hash = ToyHash::new(16);
+  assert!(hash.hash(b"YELLOW SUBMARINE") == hash.hash(b"YELLOW SUBMARINE"));
+  assert!(hash.hash(b"YELLOW SUBMARINE") < 1 << 16);
+  assert!(ToyHash::new(32).hash(b"YELLOW SUBMARINE") != ToyHash::new(32).hash(b"YELLOW SUBMARINF"));
+  assert!(md_hash::md_padding(0).len() == BLOCK_SIZE);
+  assert!(md_hash::md_padding(7).len() == 9);
+  assert!(md_hash::md_padding(9).len() == 23);
+  assert!(hash.compressions() == 6);
+}
+
+#[test]
+fn birthday_collision() {
+  for bits in [16, 24] {
+    let hash = ToyHash::new(bits);
+    let collision = md_hash::find_collision(&hash, hash.initial_state());
+    assert!(collision.first != collision.second);
+    assert!(hash.compress(hash.initial_state(), &collision.first) == collision.state);
+    assert!(hash.compress(hash.initial_state(), &collision.second) == collision.state);
+  }
+}
+
+#[test]
+fn challenge_52_multicollision() {
+  let hash = ToyHash::new(16);
+  let collisions = md_hash::joux_multicollision(&hash, 5);
+  let messages: Vec<Vec<u8>> =
+    (0..1 << 5).map(|v| md_hash::multicollision_message(&collisions, v)).collect();
+  assert!(messages.iter().all(|v| hash.hash(v) == hash.hash(&messages[0])));
+  assert!((1..messages.len()).all(|v| !messages[..v].contains(&messages[v])));
+}
+
+#[test]
+fn challenge_52_concatenated() {
+  let (cheap, expensive) = (ToyHash::new(16), ToyHash::new(24));
+  let (first, second, stats) = md_hash::concatenated_collision(&cheap, &expensive);
+  assert!(first != second);
+  assert!(cheap.hash(&first) == cheap.hash(&second));
+  assert!(expensive.hash(&first) == expensive.hash(&second));
+  assert!(stats.collisions >= 12 && stats.candidates < 1 << (stats.collisions + 1));
+  // far below the 2^20 calls a generic birthday attack on the 40-bit concatenation would need
+  assert!(stats.cheap_compressions + stats.expensive_compressions < 1 << 17);
+}
+
+#[test]
+fn expandable_message() {
+  let hash = ToyHash::new(16);
+  let expandable = md_hash::expandable_message(&hash, hash.initial_state(), 4);
+  assert!(expandable.min_blocks() == 4 && expandable.max_blocks() == 19);
+  for blocks in 4..=19 {
+    let message = expandable.produce(blocks);
+    assert!(message.len() == blocks * BLOCK_SIZE);
+    assert!(hash.compress_blocks(hash.initial_state(), &message) == expandable.state);
+  }
+}
+
+#[test]
+fn challenge_53() {
+  let hash = ToyHash::new(24);
+  let message: Vec<u8> = rand::random_iter().take(BLOCK_SIZE << 10).collect();
+  let forged = md_hash::second_preimage(&hash, &message);
+  assert!(forged != message && forged.len() == message.len());
+  assert!(hash.hash(&forged) == hash.hash(&message));
+}
+
+#[test]
+fn challenge_54() {
+  let hash = ToyHash::new(20);
+  let diamond = md_hash::Diamond::build(&hash, 6);
+  assert!(diamond.leaves.len() == 64 && diamond.blocks.len() == 6);
+  let committed = diamond.commit(&hash, 3);
+  // reuse the diamond from disk, the prediction only shows up after the commitment
+  let path = std::env::temp_dir().join(format!("diamond_{}.txt", std::process::id()));
+  diamond.save(path.to_str().unwrap()).unwrap();
+  let loaded = md_hash::Diamond::load(path.to_str().unwrap()).unwrap();
+  std::fs::remove_file(&path).unwrap();
+  assert!(loaded == diamond);
+  let prediction = b"Final score: Giants 3, Dodgers 2";
+  let message = loaded.herd(&hash, prediction, 3);
+  assert!(message.starts_with(prediction));
+  assert!(hash.hash(&message) == committed);
+}
+
+#[test]
+fn diamond_load_malformed() {
+  let path = std::env::temp_dir().join(format!("diamond_malformed_{}.txt", std::process::id()));
+  // one leaf under 64 empty levels
+  std::fs::write(&path, format!("20 00000000\n00000000\n{}", "\n".repeat(64))).unwrap();
+  let res = md_hash::Diamond::load(path.to_str().unwrap());
+  std::fs::remove_file(&path).unwrap();
+  assert!(res.unwrap_err().kind() == std::io::ErrorKind::InvalidData);
+}
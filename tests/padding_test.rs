@@ -1,4 +1,5 @@
 use cryptopals_challeges_rs::cryptog::{InvalidPadding, pkcs7_padding, validate_undo_pkcs7_padding};
+use cryptopals_challeges_rs::htb64::hex;
 
 #[test]
 fn challenge_9() {
@@ -11,7 +12,7 @@ fn challenge_9() {
 fn small_block_size() {
   let data = vec![0x01,0x02,0x03,0x04];
   let res = pkcs7_padding(data,20);
-  let expected = hex::encode(vec![0x01,0x02,0x03,0x04]) + &vec!["10".to_owned();16].join(&"".to_owned());
+  let expected = hex::encode(vec![0x01,0x02,0x03,0x04]) + &vec!["10".to_owned();16].join("");
   assert!( hex::encode(&res) == expected );
 }
 
@@ -29,7 +30,7 @@ fn big_message() {
   let mut data:Vec<u8> = vec![0;111_111];
   rand::fill(&mut data[..]);
   let res_data = pkcs7_padding(data.clone(),32);
-  let expected = hex::encode(data.clone()) + &vec!["19".to_owned();25].join(&"".to_owned());
+  let expected = hex::encode(data.clone()) + &vec!["19".to_owned();25].join("");
   assert!( hex::encode(&res_data) == expected );
 }
 
@@ -45,8 +46,8 @@ fn invalid_padding_simple() {
 fn validate_valid_padding() {
   let data:Vec<u8> = "I can't remember anything\x03\x03\x03".as_bytes().to_owned();
   let res = validate_undo_pkcs7_padding(&data);
-  assert!(!res.is_err());
-  assert!(&res.ok().unwrap() == &data[0..25])
+  assert!(res.is_ok());
+  assert!(res.ok().unwrap() == data[0..25])
 }
 
 #[test]
@@ -0,0 +1,45 @@
+use cryptopals_challeges_rs::{cryptog, htb64::hex, oracle, oracle_hacker};
+
+#[test]
+fn rc4_known_answers() {
+  assert!(hex::encode(cryptog::rc4(b"Key", b"Plaintext")) == "bbf316e8d940af0ad3");
+  assert!(hex::encode(cryptog::rc4(b"Wiki", b"pedia")) == "1021bf0420");
+  assert!(hex::encode(cryptog::rc4(b"Secret", b"Attack at dawn")) == "45a01f645fc35b383552544b9bf5");
+  assert!(cryptog::rc4(b"Secret", &cryptog::rc4(b"Secret", b"Attack at dawn")) == b"Attack at dawn");
+}
+
+#[test]
+#[should_panic(expected = "RC4 keys are 1 to 256 bytes long")]
+fn rc4_empty_key() {
+  cryptog::rc4(b"", b"Plaintext");
+}
+
+#[test]
+fn rc4_bias_attack_exaggerated_biases() {
+  let secret = b"Rollin' in my 5.0 with my ragtop";
+  // random keystream where the biased positions hit their favourite value far too often
+  let oracle_fn = |request: &[u8]| -> Vec<u8> {
+    let mut keystream: Vec<u8> = rand::random_iter().take(request.len() + secret.len()).collect();
+    for (position, bias) in [(1, 0x00), (15, 0xf0), (31, 0xe0)] {
+      if position < keystream.len() && rand::random_ratio(1, 8) {
+        keystream[position] = bias;
+      }
+    }
+    request.iter().chain(secret.iter()).zip(keystream).map(|(a, b)| a ^ b).collect()
+  };
+  assert!(oracle_hacker::rc4_bias_attack(secret.len(), 5000, oracle_fn) == secret);
+}
+
+// only the first two cookie bytes, both line up with the strong z2 bias
+#[test]
+fn challenge_56_first_bytes() {
+  let res = oracle_hacker::rc4_bias_attack(2, 1 << 16, oracle::oracle_rc4_cookie);
+  assert!(res == b"BE");
+}
+
+#[test]
+#[ignore = "needs 2^24 encryptions per prefix length, run with --ignored"]
+fn challenge_56() {
+  let res = oracle_hacker::rc4_bias_attack(30, 1 << 24, oracle::oracle_rc4_cookie);
+  assert!(res == b"BE SURE TO DRINK YOUR OVALTINE");
+}
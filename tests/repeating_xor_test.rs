@@ -1,4 +1,4 @@
-use cryptopals_challeges_rs::xor;
+use cryptopals_challeges_rs::{htb64::hex, xor};
 
 #[test]
 fn simple_key() {
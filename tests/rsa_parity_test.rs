@@ -0,0 +1,29 @@
+use cryptopals_challeges_rs::{htb64::base64, oracle, oracle_hacker, rsa};
+use openssl::bn::BigNum;
+
+#[test]
+fn rsa_round_trip() {
+  let (public_key, private_key) = rsa::generate_keypair(1024, 65537).unwrap();
+  let ciphertext = rsa::encrypt_bytes(&public_key, "I AM HERE".as_bytes()).unwrap();
+  let plaintext = rsa::decrypt_bytes(&private_key, &ciphertext).unwrap();
+  assert!(plaintext == "I AM HERE".as_bytes());
+}
+
+#[test]
+fn challenge_46() {
+  let plaintext = base64::STANDARD
+    .decode("VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==")
+    .unwrap();
+  let (public_key, private_key) = rsa::generate_keypair(1024, 65537).unwrap();
+  let ciphertext = rsa::encrypt(&public_key, &BigNum::from_slice(&plaintext).unwrap()).unwrap();
+
+  let mut steps = 0;
+  let res = oracle_hacker::rsa_parity_attack(
+    &ciphertext,
+    &public_key,
+    |c| oracle::oracle_rsa_parity(c, &private_key),
+    |_| steps += 1,
+  );
+  assert!(steps == 1024);
+  assert!(res == plaintext);
+}
@@ -0,0 +1,66 @@
+use cryptopals_challeges_rs::scoring::{
+  ChiSquared, LetterFrequency, NgramLogLikelihood, Scorer, WordDictionary,
+};
+
+const ENGLISH: &[u8] =
+  b"Now that the party is jumping, with the bass kicked in and the vegas are pumpin'";
+const GIBBERISH: &[u8] =
+  b"Xq zjv kwpx qzz jxkq vvz wqxj pzkf qjxz bvq zxw jqk vxz pqjz kxq wzjv xqzp jvkx";
+
+fn random_bytes() -> Vec<u8> {
+  (0..ENGLISH.len()).map(|_| rand::random()).collect()
+}
+
+fn ranks_english_first(scorer: &dyn Scorer) {
+  assert!(scorer.score(ENGLISH) > scorer.score(GIBBERISH));
+  assert!(scorer.score(ENGLISH) > scorer.score(&random_bytes()));
+}
+
+#[test]
+fn letter_frequency() {
+  ranks_english_first(&LetterFrequency);
+  assert!(LetterFrequency.score(b"") == 0.0);
+  assert!(LetterFrequency.score(b"ETAOIN") == LetterFrequency.score(b"etaoin"));
+}
+
+#[test]
+fn chi_squared() {
+  ranks_english_first(&ChiSquared);
+  assert!(ChiSquared.score(ENGLISH) <= 0.0);
+  assert!(
+    ChiSquared.score(b"the cat sat on the mat")
+      > ChiSquared.score(b"the\x00cat\x01sat\x02on\x03the")
+  );
+}
+
+#[test]
+fn ngram_log_likelihood() {
+  let digrams = NgramLogLikelihood::digrams();
+  let trigrams = NgramLogLikelihood::trigrams();
+  assert!(digrams.n() == 2 && digrams.len() == 676);
+  assert!(trigrams.n() == 3 && trigrams.len() == 17556);
+  ranks_english_first(&digrams);
+  ranks_english_first(&trigrams);
+  assert!(trigrams.score(b"THE") > trigrams.score(b"JQL"));
+}
+
+#[test]
+fn word_dictionary() {
+  let words = WordDictionary::english();
+  assert!(!words.is_empty());
+  assert!(words.contains(b"the") && words.contains(b"THE"));
+  ranks_english_first(&words);
+  assert!(words.score(b"the") == 1.0);
+  assert!(words.score(b"") == 0.0);
+  let custom = WordDictionary::from_list("vanilla\nice\n");
+  assert!(custom.len() == 2 && custom.score(b"vanilla ice") > 0.9);
+}
+
+// the tables are compiled in, so they load whatever the working directory
+#[test]
+fn embedded_tables() {
+  assert!(!NgramLogLikelihood::digrams().is_empty());
+  assert!(NgramLogLikelihood::trigrams().len() == 17556);
+  assert!(!WordDictionary::english().is_empty());
+  assert!(WordDictionary::english().score(ENGLISH) > 0.0);
+}
@@ -0,0 +1,162 @@
+use cryptopals_challeges_rs::{
+  cryptog,
+  htb64::{base64, hex},
+  scoring::{ChiSquared, LetterFrequency},
+  xor,
+  xor_attack::{
+    Charset, KeysizeEstimator, break_fixed_nonce_ctr, break_repeating_xor, crack_single_byte_xor,
+    crack_single_byte_xor_with, detect_single_byte_xor, index_of_coincidence, transpose,
+  },
+};
+
+const CHALLENGE_3: &str = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
+
+fn challenge_3_ciphertext() -> Vec<u8> {
+  hex::decode(CHALLENGE_3).unwrap()
+}
+
+#[test]
+fn challenge_3() {
+  let candidates = crack_single_byte_xor(&challenge_3_ciphertext(), &ChiSquared);
+  assert!(candidates[0].key == b'X');
+  assert!(candidates[0].plaintext == b"Cooking MC's like a pound of bacon");
+  assert!(candidates.windows(2).all(|v| v[0].score >= v[1].score));
+}
+
+#[test]
+fn charset_filters() {
+  let ciphertext = challenge_3_ciphertext();
+  let any = crack_single_byte_xor(&ciphertext, &LetterFrequency);
+  assert!(any.len() == 256);
+  let printable = crack_single_byte_xor_with(&ciphertext, &LetterFrequency, &Charset::printable());
+  assert!(printable.len() < any.len());
+  assert!(printable.iter().all(|v| Charset::printable().accepts(&v.plaintext)));
+  let prose = crack_single_byte_xor_with(&ciphertext, &LetterFrequency, &Charset::prose());
+  assert!(prose.len() < printable.len() && prose[0].key == b'X');
+  let none = crack_single_byte_xor_with(&ciphertext, &LetterFrequency, &Charset::from_bytes(b""));
+  assert!(none.is_empty());
+}
+
+#[test]
+fn binary_plaintext() {
+  let plaintext: Vec<u8> = (0..=255).collect();
+  let ciphertext = xor::xor_single_byte(&plaintext, 0x42);
+  let candidates = crack_single_byte_xor_with(&ciphertext, &ChiSquared, &Charset::any());
+  assert!(candidates.iter().any(|v| v.key == 0x42 && v.plaintext == plaintext));
+}
+
+#[test]
+fn challenge_4() {
+  let lines: Vec<Vec<u8>> = std::fs::read_to_string("res/challenge_4.txt")
+    .unwrap()
+    .lines()
+    .map(|v| hex::decode(v).unwrap())
+    .collect();
+  let detected = detect_single_byte_xor(&lines, &ChiSquared, &Charset::printable());
+  let (idx, top) = &detected[0];
+  assert!(*idx == 170);
+  assert!(top.key == b'5' && top.plaintext == b"Now that the party is jumping\n");
+}
+
+fn challenge_6_ciphertext() -> Vec<u8> {
+  let text: String = std::fs::read_to_string("res/challenge_6.txt").unwrap().lines().collect();
+  base64::STANDARD.decode(text).unwrap()
+}
+
+#[test]
+fn keysize_estimators() {
+  let ciphertext = challenge_6_ciphertext();
+  for estimator in [
+    KeysizeEstimator::Hamming,
+    KeysizeEstimator::IndexOfCoincidence,
+    KeysizeEstimator::Autocorrelation,
+  ] {
+    let ranked = estimator.rank(&ciphertext, 2..=40);
+    assert!(ranked.len() == 39);
+    assert!(ranked.iter().take(5).any(|(ks, _)| *ks == 29));
+  }
+  assert!(index_of_coincidence(b"aaaa") == 1.0 && index_of_coincidence(b"abcd") == 0.0);
+  assert!(transpose(b"abcde", 2) == vec![b"ace".to_vec(), b"bd".to_vec()]);
+}
+
+#[test]
+fn challenge_6() {
+  let ciphertext = challenge_6_ciphertext();
+  for estimator in [
+    KeysizeEstimator::Hamming,
+    KeysizeEstimator::IndexOfCoincidence,
+    KeysizeEstimator::Autocorrelation,
+  ] {
+    let solutions = break_repeating_xor(&ciphertext, &ChiSquared, estimator);
+    assert!(solutions[0].keysize == 29);
+    assert!(solutions[0].key == b"Terminator X: Bring the noise");
+    assert!(solutions[0].plaintext.starts_with(b"I'm back and I'm ringin' the bell"));
+  }
+}
+
+#[test]
+fn non_utf8_plaintext() {
+  // Latin-1 encoded text is not valid UTF-8
+  let plaintext =
+    b"Le caf\xe9 est tr\xe8s bon, mais la cr\xe8me br\xfbl\xe9e est encore meilleure. ".repeat(6);
+  assert!(String::from_utf8(plaintext.clone()).is_err());
+  let ciphertext = xor::xor_repeating_key(b"SECRET", &plaintext);
+  let solutions = break_repeating_xor(&ciphertext, &ChiSquared, KeysizeEstimator::Hamming);
+  assert!(solutions[0].key == b"SECRET" && solutions[0].plaintext == plaintext);
+}
+
+fn challenge_20_ciphertexts() -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+  let key: Vec<u8> = rand::random_iter().take(16).collect();
+  let plaintexts: Vec<Vec<u8>> = std::fs::read_to_string("res/challenge_20.txt")
+    .unwrap()
+    .lines()
+    .map(|v| base64::STANDARD.decode(v).unwrap())
+    .collect();
+  let ciphertexts = plaintexts.iter().map(|v| cryptog::aes_ctr(0, &key, v)).collect();
+  (plaintexts, ciphertexts)
+}
+
+#[test]
+fn challenge_20() {
+  let (plaintexts, ciphertexts) = challenge_20_ciphertexts();
+  let keystream = break_fixed_nonce_ctr(&ciphertexts, &ChiSquared);
+  let longest = ciphertexts.iter().map(|v| v.len()).max().unwrap();
+  assert!(keystream.len() == longest);
+  assert!(keystream.coverage[0] == ciphertexts.len() && keystream.coverage[longest - 1] == 1);
+  // the columns shared by every ciphertext decrypt exactly
+  let shortest = ciphertexts.iter().map(|v| v.len()).min().unwrap();
+  for (plaintext, ciphertext) in plaintexts.iter().zip(&ciphertexts) {
+    assert!(keystream.decrypt(ciphertext)[..shortest] == plaintext[..shortest]);
+  }
+  assert!(keystream.confidence[..shortest].iter().all(|v| *v > 0.0));
+}
+
+#[test]
+fn fixed_nonce_ctr_overrides() {
+  let (plaintexts, ciphertexts) = challenge_20_ciphertexts();
+  let mut keystream = break_fixed_nonce_ctr(&ciphertexts, &ChiSquared);
+  let (idx, longest) = ciphertexts.iter().enumerate().max_by_key(|(_, v)| v.len()).unwrap();
+  keystream.set_plaintext(longest, 0, &plaintexts[idx]);
+  assert!(keystream.decrypt(longest) == plaintexts[idx]);
+  assert!(keystream.uncertain(f64::INFINITY).is_empty());
+  for (plaintext, ciphertext) in plaintexts.iter().zip(&ciphertexts) {
+    assert!(keystream.decrypt(ciphertext) == *plaintext);
+  }
+  keystream.set(0, keystream.bytes[0] ^ 1);
+  assert!(keystream.decrypt(&ciphertexts[0])[0] == plaintexts[0][0] ^ 1);
+  // out of range columns are ignored
+  let before = keystream.clone();
+  keystream.set(longest.len(), 0);
+  keystream.set_plaintext(longest, longest.len() + 5, b"past the end");
+  keystream.set_plaintext(&ciphertexts[0], 0, &[0; 1000]);
+  assert!(keystream.len() == before.len());
+  assert!(keystream.bytes[ciphertexts[0].len()..] == before.bytes[ciphertexts[0].len()..]);
+}
+
+#[test]
+fn fixed_nonce_ctr_ragged() {
+  let ciphertexts: Vec<Vec<u8>> = vec![];
+  assert!(break_fixed_nonce_ctr(&ciphertexts, &ChiSquared).is_empty());
+  let keystream = break_fixed_nonce_ctr(&[b"a".to_vec(), vec![], b"abc".to_vec()], &ChiSquared);
+  assert!(keystream.len() == 3 && keystream.coverage == vec![2, 1, 1]);
+}